@@ -1,104 +1,198 @@
 #![allow(unused)]
 use std::collections::HashMap;
+use std::sync::OnceLock;
 
-use crate::util::DaySolver;
+use aho_corasick::{AhoCorasick, Match};
+
+use crate::util::input::Input;
+use crate::util::{AocError, DaySolver, Result};
 
 type Solution = u32;
 
-pub struct Day1();
+/// Finds the first and last digits of a line, written in any of the words of a vocabulary
+pub struct DigitScanner {
+    automaton: AhoCorasick,
+    values: Vec<u32>,
+    /// The length of the longest word
+    longest: usize,
+}
 
-impl Day1 {
-    fn calibration_number(line: &str) -> u32 {
-        let pairs = [
-            ('0', 0),
-            ('1', 1),
-            ('2', 2),
-            ('3', 3),
-            ('4', 4),
-            ('5', 5),
-            ('6', 6),
-            ('7', 7),
-            ('8', 8),
-            ('9', 9),
-        ];
-        let f = line
-            .chars()
-            .find_map(|c| pairs.iter().find(|(p1, p2)| *p1 == c).map(|(p1, p2)| p2))
-            .unwrap();
-        let l = line
-            .chars()
-            .rev()
-            .find_map(|c| pairs.iter().find(|(p1, p2)| *p1 == c).map(|(p1, p2)| p2))
-            .unwrap();
-        // let l = line
-        //     .chars()
-        //     .filter(|c| "0123456789".contains(*c))
-        //     .last()
-        //     .unwrap()
-        //     .to_digit(10)
-        //     .unwrap();
-        (f * 10) + l
-    }
-    fn with_spelling(line: &str) -> Option<u32> {
-        let pairs = [
-            ("0", 0),
-            ("1", 1),
-            ("2", 2),
-            ("3", 3),
-            ("4", 4),
-            ("5", 5),
-            ("6", 6),
-            ("7", 7),
-            ("8", 8),
-            ("9", 9),
-            ("zero", 0),
-            ("one", 1),
-            ("two", 2),
-            ("three", 3),
-            ("four", 4),
-            ("five", 5),
-            ("six", 6),
-            ("seven", 7),
-            ("eight", 8),
-            ("nine", 9),
-        ];
-        for (start, digit) in pairs {
-            if line.starts_with(start) {
-                return Some(digit);
+impl DigitScanner {
+    /// Scan for the words of `vocabulary`, each of which stands for the digit it is paired with.
+    /// Words may overlap, like "eightwo".
+    pub fn new<'a>(vocabulary: impl IntoIterator<Item = (&'a str, u32)>) -> Result<Self> {
+        let (words, values): (Vec<&str>, Vec<u32>) = vocabulary.into_iter().unzip();
+        let automaton = AhoCorasick::new(&words)
+            .map_err(|e| AocError::solver(format!("cannot scan for those words: {e}")))?;
+        Ok(DigitScanner {
+            automaton,
+            values,
+            longest: words.iter().map(|word| word.len()).max().unwrap_or_default(),
+        })
+    }
+    /// The digits 0 through 9
+    pub fn digits() -> Self {
+        Self::new(DIGITS).expect("the digits are a valid vocabulary")
+    }
+    /// The digits, and the digits spelled out in English
+    pub fn english() -> Self {
+        Self::new(DIGITS.into_iter().chain(ENGLISH))
+            .expect("the English digits are a valid vocabulary")
+    }
+    pub fn first(&self, line: &str) -> Option<u32> {
+        let mut first: Option<Match> = None;
+        for found in self.automaton.find_overlapping_iter(line) {
+            match first {
+                // Matches are found in the order that they end. Once they end far enough along,
+                // none of the rest can start before the first one.
+                Some(first) if found.end() > first.start() + self.longest => break,
+                Some(first) if found.start() >= first.start() => {}
+                _ => first = Some(found),
             }
         }
-        None
-    }
-    fn spelling_calibration_number(line: &str) -> u32 {
-        let mut first = None;
-        let mut last = None;
-        for i in 0..line.len() {
-            if let Some(num) = Self::with_spelling(&line[i..]) {
-                if first.is_none() {
-                    first = Some(num);
-                }
-                last = Some(num);
-            }
+        first.map(|found| self.values[found.pattern()])
+    }
+    pub fn last(&self, line: &str) -> Option<u32> {
+        self.automaton
+            .find_overlapping_iter(line)
+            .max_by_key(|found| found.start())
+            .map(|found| self.values[found.pattern()])
+    }
+    /// The first digit and the last digit, read as a two digit number
+    pub fn calibration_number(&self, line: &str) -> Result<u32> {
+        match (self.first(line), self.last(line)) {
+            (Some(first), Some(last)) => Ok((first * 10) + last),
+            _ => Err(AocError::parse(0, "no digits on this line")),
         }
-        (first.unwrap() * 10) + last.unwrap()
+    }
+}
+
+/// What to do about lines without any digits
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Strictness {
+    /// Fail on the first line without a digit
+    #[default]
+    Strict,
+    /// Leave lines without a digit out of the total
+    Lenient,
+}
+
+impl DigitScanner {
+    /// The calibration value of every line of the document, or which line has none and why
+    pub fn calibrate<'a>(&'a self, document: &'a str) -> impl Iterator<Item = Result<u32>> + 'a {
+        Input::new(document)
+            .numbered_lines()
+            .map(|(y, line)| self.calibration_number(line).map_err(|e| e.on_line(y)))
+    }
+    /// The sum of the calibration values of the document
+    pub fn total(&self, document: &str, strictness: Strictness) -> Result<u32> {
+        self.calibrate(document)
+            .filter(|value| strictness == Strictness::Strict || value.is_ok())
+            .sum()
+    }
+}
+
+const DIGITS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+const ENGLISH: [(&str, u32); 10] = [
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+pub struct Day1();
+
+static STRICTNESS: OnceLock<Strictness> = OnceLock::new();
+
+impl Day1 {
+    /// Choose what to do about lines without digits for the rest of the run. Every line of a real
+    /// input has a digit, but the example for part 2 has lines that only spell them out, which
+    /// part 1 can't read. Can only be called once, before anything is solved.
+    pub fn select_strictness(strictness: Strictness) -> Result<()> {
+        STRICTNESS
+            .set(strictness)
+            .map_err(|_| AocError::Usage("the strictness was already selected".to_string()))
+    }
+    fn strictness() -> Strictness {
+        STRICTNESS.get().copied().unwrap_or_default()
     }
 }
 
 impl DaySolver<Solution> for Day1 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        Some(
-            input
-                .iter()
-                .map(|line| Self::calibration_number(line))
-                .sum::<u32>(),
-        )
-    }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        Some(
-            input
-                .iter()
-                .map(|line| Self::spelling_calibration_number(line))
-                .sum::<u32>(),
-        )
+    // Each part reads the digits differently, so the lines are kept as they are
+    type Parsed = String;
+
+    fn parse(input: &str) -> Result<String> {
+        Ok(input.to_string())
+    }
+    fn part1(input: &String) -> Result<Solution> {
+        DigitScanner::digits().total(input, Self::strictness())
+    }
+    fn part2(input: &String) -> Result<Solution> {
+        DigitScanner::english().total(input, Self::strictness())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_overlapping_words() {
+        let scanner = DigitScanner::english();
+        assert_eq!(scanner.calibration_number("eightwothree").unwrap(), 83);
+        assert_eq!(scanner.calibration_number("zoneight234").unwrap(), 14);
+        assert_eq!(scanner.calibration_number("7pqrstsixteen").unwrap(), 76);
+        assert_eq!(scanner.last("twone"), Some(1));
+        assert!(DigitScanner::digits().calibration_number("two").is_err());
+    }
+
+    #[test]
+    fn test_vocabulary() {
+        // A word that contains another, which starts later but ends first
+        let scanner = DigitScanner::new([("seventeen", 7), ("vent", 1), ("teen", 2)]).unwrap();
+        assert_eq!(scanner.first("xseventeen"), Some(7));
+        assert_eq!(scanner.last("xseventeen"), Some(2));
+        let german = DigitScanner::new([("eins", 1), ("zwei", 2), ("drei", 3)]).unwrap();
+        assert_eq!(german.calibration_number("zweins").unwrap(), 21);
+    }
+
+    #[test]
+    fn test_strictness() {
+        let document = "two1nine\neightwothree\nabcone2threexyz";
+        let scanner = DigitScanner::digits();
+        let values: Vec<_> = scanner.calibrate(document).collect();
+        assert_eq!(values[0].as_ref().unwrap(), &11);
+        assert_eq!(
+            values[1].as_ref().unwrap_err().to_string(),
+            AocError::parse(0, "no digits on this line")
+                .on_line(1)
+                .to_string()
+        );
+        assert_eq!(scanner.total(document, Strictness::Lenient).unwrap(), 33);
+        assert!(scanner.total(document, Strictness::Strict).is_err());
+        assert_eq!(
+            DigitScanner::english()
+                .total(document, Strictness::Strict)
+                .unwrap(),
+            29 + 83 + 13
+        );
     }
 }
@@ -1,31 +1,14 @@
 #![allow(unused)]
 use std::collections::HashMap;
 
-use crate::util::DaySolver;
+use crate::util::grid::{Dir, Grid, Pos};
+use crate::util::input::Input;
+use crate::util::{AocError, DaySolver, Result};
 
 type Solution = usize;
 
 pub struct Day10();
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum Direction {
-    N,
-    S,
-    E,
-    W,
-}
-
-impl Direction {
-    pub fn walk(&self, (y, x): (usize, usize)) -> (usize, usize) {
-        match self {
-            Direction::N => (y - 1, x),
-            Direction::S => (y + 1, x),
-            Direction::E => (y, x + 1),
-            Direction::W => (y, x - 1),
-        }
-    }
-}
-
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Pipe {
     NS,
@@ -37,7 +20,7 @@ pub enum Pipe {
 }
 
 impl TryFrom<char> for Pipe {
-    fn try_from(value: char) -> Result<Self, Self::Error> {
+    fn try_from(value: char) -> std::result::Result<Self, Self::Error> {
         match value {
             '|' => Ok(Pipe::NS),
             'L' => Ok(Pipe::NE),
@@ -58,181 +41,144 @@ impl Pipe {
     /// The returned direction is where you would go if you entered the pipe walkin in dir
     /// direction.
     /// For example, entering a SE walking North would result in you walking East.
-    pub fn follow(&self, dir: &Direction) -> Direction {
-        match dir {
-            Direction::N => match self {
-                Pipe::NS => Direction::N,
-                Pipe::SE => Direction::E,
-                Pipe::SW => Direction::W,
-                _ => panic!(),
+    /// Returns `None` if the pipe has no end facing back the way you came.
+    pub fn follow(&self, dir: &Dir) -> Option<Dir> {
+        let dir = match dir {
+            Dir::N => match self {
+                Pipe::NS => Dir::N,
+                Pipe::SE => Dir::E,
+                Pipe::SW => Dir::W,
+                _ => return None,
             },
-            Direction::S => match self {
-                Pipe::NS => Direction::S,
-                Pipe::NE => Direction::E,
-                Pipe::NW => Direction::W,
-                _ => panic!(),
+            Dir::S => match self {
+                Pipe::NS => Dir::S,
+                Pipe::NE => Dir::E,
+                Pipe::NW => Dir::W,
+                _ => return None,
             },
-            Direction::E => match self {
-                Pipe::EW => Direction::E,
-                Pipe::NW => Direction::N,
-                Pipe::SW => Direction::S,
-                _ => panic!(),
+            Dir::E => match self {
+                Pipe::EW => Dir::E,
+                Pipe::NW => Dir::N,
+                Pipe::SW => Dir::S,
+                _ => return None,
             },
-            Direction::W => match self {
-                Pipe::EW => Direction::W,
-                Pipe::NE => Direction::N,
-                Pipe::SE => Direction::S,
-                _ => panic!(),
+            Dir::W => match self {
+                Pipe::EW => Dir::W,
+                Pipe::NE => Dir::N,
+                Pipe::SE => Dir::S,
+                _ => return None,
             },
-        }
-    }
-    pub fn c(&self) -> char {
-        match self {
-            Pipe::NS => '│',
-            Pipe::NE => '└',
-            Pipe::NW => '┘',
-            Pipe::SE => '┌',
-            Pipe::SW => '┐',
-            Pipe::EW => '─',
-        }
+        };
+        Some(dir)
     }
 }
 
 pub struct Field {
-    pipes: Vec<Vec<Option<Pipe>>>,
-    /// (y, x)
-    start: (usize, usize),
+    pipes: Grid<Option<Pipe>>,
+    start: Pos,
 }
 
 impl Field {
-    pub fn transit(&self, transit: &Transit) -> Transit {
-        let dir = self.pipes[transit.y][transit.x]
+    /// The pipe that `transit` is entering
+    fn pipe(&self, transit: &Transit) -> Result<&Pipe> {
+        self.pipes[transit.pos]
             .as_ref()
-            .unwrap()
-            .follow(&transit.dir);
-        let (y, x) = dir.walk((transit.y, transit.x));
-        Transit { x, y, dir }
+            .ok_or_else(|| transit.broken())
     }
-    pub fn starting_transits(&self) -> (Transit, Transit) {
-        let (y, x) = self.start;
-        let mut starts = [Transit {
-            x: 0,
-            y: 0,
-            dir: Direction::N,
-        }; 2];
-        let mut index = 0;
-        // N
-        if [Some(Pipe::NS), Some(Pipe::SE), Some(Pipe::SW)].contains(&self.pipes[y - 1][x]) {
-            starts[index] = Transit {
-                x,
-                y: y - 1,
-                dir: Direction::N,
-            };
-            index += 1;
-        }
-        // S
-        if [Some(Pipe::NS), Some(Pipe::NE), Some(Pipe::NW)].contains(&self.pipes[y + 1][x]) {
-            starts[index] = Transit {
-                x,
-                y: y + 1,
-                dir: Direction::S,
-            };
-            index += 1;
-        }
-        // E
-        if [Some(Pipe::EW), Some(Pipe::NW), Some(Pipe::SW)].contains(&self.pipes[y][x + 1]) {
-            starts[index] = Transit {
-                x: x + 1,
-                y,
-                dir: Direction::E,
-            };
-            index += 1;
-        }
-        // W
-        if [Some(Pipe::EW), Some(Pipe::NE), Some(Pipe::SE)].contains(&self.pipes[y][x - 1]) {
-            starts[index] = Transit {
-                x: x - 1,
-                y,
-                dir: Direction::W,
-            };
-            index += 1;
-        }
-        (starts[0], starts[1])
+    pub fn transit(&self, transit: &Transit) -> Result<Transit> {
+        let dir = self
+            .pipe(transit)?
+            .follow(&transit.dir)
+            .ok_or_else(|| transit.broken())?;
+        let pos = self
+            .pipes
+            .step(transit.pos, dir)
+            .ok_or_else(|| transit.broken())?;
+        Ok(Transit { pos, dir })
+    }
+    /// The two pipes that the start connects to, and which way they are entered
+    pub fn starting_transits(&self) -> Option<(Transit, Transit)> {
+        let mut starts = [Dir::N, Dir::S, Dir::E, Dir::W]
+            .into_iter()
+            .filter_map(|dir| {
+                let pos = self.pipes.step(self.start, dir)?;
+                // The pipe must have an end facing back towards the start
+                let connects = match dir {
+                    Dir::N => [Pipe::NS, Pipe::SE, Pipe::SW],
+                    Dir::S => [Pipe::NS, Pipe::NE, Pipe::NW],
+                    Dir::E => [Pipe::EW, Pipe::NW, Pipe::SW],
+                    Dir::W => [Pipe::EW, Pipe::NE, Pipe::SE],
+                };
+                let pipe = self.pipes[pos].as_ref()?;
+                connects.contains(pipe).then_some(Transit { pos, dir })
+            });
+        Some((starts.next()?, starts.next()?))
     }
 }
 
 #[derive(Clone, Copy, Debug)]
 pub struct Transit {
-    x: usize,
-    y: usize,
-    dir: Direction,
+    pos: Pos,
+    dir: Dir,
 }
 
-impl Day10 {
-    fn parse(input: &[String]) -> Field {
-        let mut start = (0, 0);
-        let pipes = input
-            .iter()
-            .enumerate()
-            .map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(x, c)| {
-                        Pipe::try_from(c)
-                            .map_err(|is_start| {
-                                if is_start {
-                                    start = (y, x)
-                                }
-                            })
-                            .ok()
-                    })
-                    .collect()
-            })
-            .collect();
-        Field { pipes, start }
+impl Transit {
+    /// The error for a pipe that does not lead anywhere from here
+    fn broken(&self) -> AocError {
+        AocError::solver(format!("the loop is broken at {:?}", self.pos))
     }
 }
 
 impl DaySolver<Solution> for Day10 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let field = Self::parse(&input);
-        let (y, x) = field.start;
-        let (mut a, mut b) = field.starting_transits();
+    type Parsed = Field;
+
+    fn parse(input: &str) -> Result<Field> {
+        let view = Input::new(input).grid()?;
+        let start = view
+            .position(b'S')
+            .ok_or_else(|| AocError::parse(0, "there is no S to start from"))?;
+        let pipes = Grid::from_view(&view, |c| match Pipe::try_from(char::from(c)) {
+            Ok(pipe) => Some(Some(pipe)),
+            Err(true) => Some(None),
+            Err(false) => (c == b'.').then_some(None),
+        })?;
+        let field = Field { pipes, start };
+        if field.starting_transits().is_none() {
+            return Err(AocError::parse(0, "the start does not connect to two pipes"));
+        }
+        Ok(field)
+    }
+    fn part1(field: &Field) -> Result<Solution> {
+        let (mut a, mut b) = field.starting_transits().expect("checked while parsing");
         let mut steps = 1;
-        while a.x != b.x || a.y != b.y {
-            a = field.transit(&a);
-            b = field.transit(&b);
+        while a.pos != b.pos {
+            a = field.transit(&a)?;
+            b = field.transit(&b)?;
             steps += 1;
         }
-        Some(steps)
+        Ok(steps)
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
+    fn part2(field: &Field) -> Result<Solution> {
         // Here's the algorithm:
         // Walk the path, mark all pipes that are part of the path.
         // For every row, slide along until you find a |, L, or F pipe that is part of the path.
         // Continue sliding until finding a |, 7, or J pipe. You are now inside.
         // Continue sliding, counting squares until you find a |, L, or F pipe.
         // Continue sliding until finding a |, 7, or J pipe. You are now outside. Repeat.
-        let field = Self::parse(&input);
-        let mut visited: HashMap<(usize, usize), Pipe> = HashMap::new();
-        let (start_y, start_x) = field.start;
+        let mut visited: HashMap<Pos, Pipe> = HashMap::new();
         // I'm just hardcoding this, too lazy to figure out from first principles
-        visited.insert((start_y, start_x), Pipe::NS);
-        let mut transit = field.starting_transits().0;
-        while transit.x != start_x || transit.y != start_y {
-            visited.insert(
-                (transit.y, transit.x),
-                field.pipes[transit.y][transit.x].as_ref().unwrap().clone(),
-            );
-            transit = field.transit(&transit);
+        visited.insert(field.start, Pipe::NS);
+        let mut transit = field.starting_transits().expect("checked while parsing").0;
+        while transit.pos != field.start {
+            visited.insert(transit.pos, field.pipe(&transit)?.clone());
+            transit = field.transit(&transit)?;
         }
         let mut sum = 0;
-        for y in 0..field.pipes.len() {
+        for y in 0..field.pipes.height() {
             let mut inside = false;
             let mut border_start = None;
-            for x in 0..field.pipes[0].len() {
-                if let Some(p) = visited.get(&(y, x)) {
-                    print!("{}", p.c());
+            for x in 0..field.pipes.width() {
+                if let Some(p) = visited.get(&(x, y)) {
                     if p == &Pipe::NS {
                         inside = !inside;
                     } else if let Some(start) = &border_start {
@@ -249,14 +195,10 @@ impl DaySolver<Solution> for Day10 {
                         border_start = Some(p.clone());
                     }
                 } else if inside {
-                    print!(".");
                     sum += 1;
-                } else {
-                    print!(" ");
                 }
             }
-            println!();
         }
-        Some(sum)
+        Ok(sum)
     }
 }
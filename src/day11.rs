@@ -1,9 +1,10 @@
 #![allow(unused)]
-use crate::util::DaySolver;
+use crate::util::grid::Grid;
+use crate::util::{AocError, DaySolver, Result};
 
 type Galaxy = (u64, u64);
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Observation {
     length: usize,
     galaxies: Vec<Galaxy>,
@@ -51,35 +52,35 @@ impl Observation {
 
 pub struct Day11();
 
-impl Day11 {
-    pub fn parse(input: &[String]) -> Observation {
-        let galaxies: Vec<Galaxy> = input
+type Solution = u64;
+impl DaySolver<Solution> for Day11 {
+    type Parsed = Observation;
+
+    fn parse(input: &str) -> Result<Observation> {
+        let space = Grid::parse(input, |c| match c {
+            b'#' => Some(true),
+            b'.' => Some(false),
+            _ => None,
+        })?;
+        // Galaxies are listed row by row, which expanding relies on
+        let galaxies = space
             .iter()
-            .enumerate()
-            .flat_map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .filter(|(_, c)| c == &'#')
-                    .map(move |(x, _)| (x as u64, y as u64))
-            })
+            .filter(|(_, &galaxy)| galaxy)
+            .map(|((x, y), _)| (x as u64, y as u64))
             .collect();
-        Observation {
-            length: input.len(),
+        Ok(Observation {
+            length: space.height(),
             galaxies,
-        }
+        })
     }
-}
-
-type Solution = u64;
-impl DaySolver<Solution> for Day11 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let mut obs = Self::parse(&input);
+    fn part1(obs: &Observation) -> Result<Solution> {
+        let mut obs = obs.clone();
         obs.expand(1);
-        Some(obs.distances())
+        Ok(obs.distances())
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let mut obs = Self::parse(&input);
+    fn part2(obs: &Observation) -> Result<Solution> {
+        let mut obs = obs.clone();
         obs.expand(999999);
-        Some(obs.distances())
+        Ok(obs.distances())
     }
 }
@@ -1,7 +1,7 @@
 #![allow(unused)]
 use std::collections::HashMap;
 
-use crate::util::DaySolver;
+use crate::util::{parse_field, parse_lines, AocError, DaySolver, Result};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Spring {
@@ -10,13 +10,15 @@ pub enum Spring {
     Dunno,
 }
 
-impl From<char> for Spring {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Spring {
+    type Error = AocError;
+
+    fn try_from(value: char) -> Result<Self> {
         match value {
-            '.' => Spring::Good,
-            '#' => Spring::Bad,
-            '?' => Spring::Dunno,
-            _ => panic!(),
+            '.' => Ok(Spring::Good),
+            '#' => Ok(Spring::Bad),
+            '?' => Ok(Spring::Dunno),
+            _ => Err(AocError::parse(0, format!("{value} is not a spring"))),
         }
     }
 }
@@ -43,38 +45,35 @@ pub struct Row {
     springs: Vec<Spring>,
     bads: Vec<usize>,
 }
-impl From<&String> for Row {
-    fn from(value: &String) -> Self {
+impl TryFrom<&str> for Row {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self> {
         let bytes = value.as_bytes();
         let mut springs = Vec::with_capacity(25);
         let mut i = 0;
-        while bytes[i] != b' ' {
-            springs.push(Spring::from(char::from(bytes[i])));
+        while i < bytes.len() && bytes[i] != b' ' {
+            springs.push(Spring::try_from(char::from(bytes[i])).map_err(|_| {
+                AocError::parse(i, format!("{} is not a spring", char::from(bytes[i])))
+            })?);
             i += 1;
         }
+        if i == bytes.len() {
+            return Err(AocError::parse(i, "expected a space before the counts"));
+        }
         i += 1;
         let mut bads = Vec::with_capacity(6);
         let mut start = i;
         while i < bytes.len() {
             if bytes[i] == b',' {
-                bads.push(
-                    std::str::from_utf8(&bytes[start..i])
-                        .unwrap()
-                        .parse()
-                        .unwrap(),
-                );
+                bads.push(parse_field(value, &value[start..i])?);
                 i += 1;
                 start = i;
             }
             i += 1;
         }
-        bads.push(
-            std::str::from_utf8(&bytes[start..])
-                .unwrap()
-                .parse()
-                .unwrap(),
-        );
-        Row { springs, bads }
+        bads.push(parse_field(value, &value[start..])?);
+        Ok(Row { springs, bads })
         // Much more legible, but slower:
         // let (left, right) = value.split_once(' ').unwrap();
         // Row {
@@ -110,13 +109,9 @@ impl Memo {
 pub struct Day12();
 
 impl Day12 {
-    pub fn parse(input: &[String]) -> Vec<Row> {
-        input.iter().map(Row::from).collect()
-    }
-    pub fn parse2(input: &[String]) -> Vec<Row> {
-        input
-            .iter()
-            .map(Row::from)
+    /// Each row of part 2 is five copies of the folded row, joined by unknown springs
+    fn unfold(rows: &[Row]) -> Vec<Row> {
+        rows.iter()
             .map(|row| {
                 let mut springs = Vec::with_capacity((row.springs.len() + 1) * 5);
                 springs.extend(row.springs.iter().copied());
@@ -218,25 +213,27 @@ impl Day12 {
 
 type Solution = u64;
 impl DaySolver<Solution> for Day12 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let rows = Self::parse(&input);
-        Some(
-            rows.iter()
-                .map(|row| Self::solve_1(&row.springs, &row.bads))
-                .sum(),
-        )
+    type Parsed = Vec<Row>;
+
+    fn parse(input: &str) -> Result<Vec<Row>> {
+        parse_lines(input, Row::try_from)
+    }
+    fn part1(rows: &Vec<Row>) -> Result<Solution> {
+        Ok(rows
+            .iter()
+            .map(|row| Self::solve_1(&row.springs, &row.bads))
+            .sum())
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let rows = Self::parse2(&input);
-        Some(
-            rows.iter()
-                .enumerate()
-                .map(|(i, row)| {
-                    let mut memo = Memo::default();
-                    Self::solve_2_memo(&mut memo, &row.springs, &row.bads)
-                })
-                .sum(),
-        )
+    fn part2(rows: &Vec<Row>) -> Result<Solution> {
+        let rows = Self::unfold(rows);
+        Ok(rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut memo = Memo::default();
+                Self::solve_2_memo(&mut memo, &row.springs, &row.bads)
+            })
+            .sum())
     }
 }
 
@@ -245,32 +242,32 @@ mod test {
     use super::*;
     #[test]
     fn test_12_a() {
-        let row = Row::from(&"???.### 1,1,3".to_string());
+        let row = Row::try_from("???.### 1,1,3").unwrap();
         assert_eq!(Day12::solve_1(&row.springs, &row.bads), 1);
     }
     #[test]
     fn test_12_b() {
-        let row = Row::from(&".??..??...?##. 1,1,3".to_string());
+        let row = Row::try_from(".??..??...?##. 1,1,3").unwrap();
         assert_eq!(Day12::solve_1(&row.springs, &row.bads), 4);
     }
     #[test]
     fn test_12_c() {
-        let row = Row::from(&"?#?#?#?#?#?#?#? 1,3,1,6".to_string());
+        let row = Row::try_from("?#?#?#?#?#?#?#? 1,3,1,6").unwrap();
         assert_eq!(Day12::solve_1(&row.springs, &row.bads), 1);
     }
     #[test]
     fn test_12_d() {
-        let row = Row::from(&"????.#...#... 4,1,1".to_string());
+        let row = Row::try_from("????.#...#... 4,1,1").unwrap();
         assert_eq!(Day12::solve_1(&row.springs, &row.bads), 1);
     }
     #[test]
     fn test_12_e() {
-        let row = Row::from(&"????.######..#####. 1,6,5".to_string());
+        let row = Row::try_from("????.######..#####. 1,6,5").unwrap();
         assert_eq!(Day12::solve_1(&row.springs, &row.bads), 4);
     }
     #[test]
     fn test_12_f() {
-        let row = Row::from(&"?###???????? 3,2,1".to_string());
+        let row = Row::try_from("?###???????? 3,2,1").unwrap();
         assert_eq!(Day12::solve_1(&row.springs, &row.bads), 10);
     }
 }
@@ -1,21 +1,21 @@
 #![allow(unused)]
-use crate::util::DaySolver;
+use crate::util::grid::Grid;
+use crate::util::input::Input;
+use crate::util::{AocError, DaySolver, Result};
 
-type Tile = char;
-
-#[derive(Default)]
 pub struct Field {
-    tiles: Vec<Vec<Tile>>,
+    tiles: Grid<u8>,
 }
 
 impl Field {
     pub fn horizontal_reflection(&self) -> Option<usize> {
-        for split in 0..self.tiles.len() - 1 {
+        let height = self.tiles.height();
+        for split in 0..height - 1 {
             // check if we have found a mirrored row
-            if self.tiles[split] == self.tiles[split + 1] {
+            if self.tiles.row(split) == self.tiles.row(split + 1) {
                 // check every other row pair
-                if (1..(split + 1).min(self.tiles.len() - split - 1))
-                    .all(|i| self.tiles[split - i] == self.tiles[split + 1 + i])
+                if (1..(split + 1).min(height - split - 1))
+                    .all(|i| self.tiles.row(split - i) == self.tiles.row(split + 1 + i))
                 {
                     return Some(split);
                 }
@@ -27,10 +27,11 @@ impl Field {
         self.invert().horizontal_reflection()
     }
     pub fn horizontal_reflection_2(&self) -> Option<usize> {
-        (0..self.tiles.len() - 1).find(|&split| {
-            (0..(split + 1).min(self.tiles.len() - split - 1)).try_fold(0, |acc, y| {
-                let smudges = (0..self.tiles[0].len())
-                    .filter(|x| self.tiles[split - y][*x] != self.tiles[split + 1 + y][*x])
+        let (width, height) = (self.tiles.width(), self.tiles.height());
+        (0..height - 1).find(|&split| {
+            (0..(split + 1).min(height - split - 1)).try_fold(0, |acc, y| {
+                let smudges = (0..width)
+                    .filter(|&x| self.tiles[(x, split - y)] != self.tiles[(x, split + 1 + y)])
                     .count();
                 if acc + smudges <= 1 {
                     Some(acc + smudges)
@@ -41,85 +42,64 @@ impl Field {
         })
     }
     pub fn vertical_reflection_2(&self) -> Option<usize> {
-        (0..self.tiles[0].len() - 1).find(|&split| {
-            (0..(split + 1).min(self.tiles[0].len() - split - 1)).try_fold(0, |acc, x| {
-                let smudges = (0..self.tiles.len())
-                    .filter(|y| self.tiles[*y][split - x] != self.tiles[*y][split + 1 + x])
-                    .count();
-                if acc + smudges <= 1 {
-                    Some(acc + smudges)
-                } else {
-                    None
-                }
-            }) == Some(1)
-        })
+        self.invert().horizontal_reflection_2()
     }
     pub fn invert(&self) -> Field {
         Field {
-            tiles: (0..self.tiles[0].len())
-                .map(|x| (0..self.tiles.len()).map(|y| self.tiles[y][x]).collect())
-                .collect(),
+            tiles: self.tiles.transpose(),
         }
     }
 }
 
 pub struct Day13();
 
-impl Day13 {
-    pub fn parse(input: &[String]) -> Vec<Field> {
-        let mut fields = vec![];
-        let mut field = Field::default();
-        for line in input.iter() {
-            if line.is_empty() {
-                fields.push(field);
-                field = Field::default();
-            } else {
-                field.tiles.push(line.chars().collect());
-            }
-        }
-        fields.push(field);
-        fields
-    }
-
-    fn solve(input: Vec<String>) -> Option<Solution> {
-        None
-    }
-}
-
 type Solution = usize;
 impl DaySolver<Solution> for Day13 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let fields = Self::parse(&input);
-        Some(
-            fields
-                .iter()
-                .map(|field| {
-                    if let Some(h) = field.horizontal_reflection() {
-                        100 * (h + 1)
-                    } else if let Some(v) = field.vertical_reflection() {
-                        v + 1
-                    } else {
-                        panic!()
-                    }
-                })
-                .sum(),
-        )
+    type Parsed = Vec<Field>;
+
+    fn parse(input: &str) -> Result<Vec<Field>> {
+        let fields: Vec<Field> = Input::new(input)
+            .paragraphs()
+            .map(|pattern| {
+                let tiles = Grid::from_view(&pattern.grid()?, |c| b"#.".contains(&c).then_some(c))?;
+                Ok(Field { tiles })
+            })
+            .collect::<Result<_>>()?;
+        if fields.is_empty() {
+            return Err(AocError::parse(0, "expected a pattern"));
+        }
+        Ok(fields)
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let fields = Self::parse(&input);
-        Some(
-            fields
-                .iter()
-                .map(|field| {
-                    if let Some(h) = field.horizontal_reflection_2() {
-                        100 * (h + 1)
-                    } else if let Some(v) = field.vertical_reflection_2() {
-                        v + 1
-                    } else {
-                        panic!()
-                    }
-                })
-                .sum(),
-        )
+    fn part1(fields: &Vec<Field>) -> Result<Solution> {
+        fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                if let Some(h) = field.horizontal_reflection() {
+                    Ok(100 * (h + 1))
+                } else if let Some(v) = field.vertical_reflection() {
+                    Ok(v + 1)
+                } else {
+                    Err(AocError::solver(format!("pattern {i} has no reflection")))
+                }
+            })
+            .sum()
+    }
+    fn part2(fields: &Vec<Field>) -> Result<Solution> {
+        fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                if let Some(h) = field.horizontal_reflection_2() {
+                    Ok(100 * (h + 1))
+                } else if let Some(v) = field.vertical_reflection_2() {
+                    Ok(v + 1)
+                } else {
+                    Err(AocError::solver(format!(
+                        "pattern {i} has no smudged reflection"
+                    )))
+                }
+            })
+            .sum()
     }
 }
@@ -1,69 +1,52 @@
 #![allow(unused)]
 use std::{collections::HashMap, hash::Hash};
 
-use crate::util::DaySolver;
-
-type Rock = char;
+use crate::util::grid::{Dir, Grid, Pos};
+use crate::util::{AocError, DaySolver, Result};
 
 #[derive(Clone, Eq, Hash, PartialEq)]
 pub struct Platform {
-    rocks: Vec<Vec<Rock>>,
+    rocks: Grid<u8>,
 }
 
 impl Platform {
+    /// Roll the round rock at `xy` as far as it goes in `dir`
+    fn roll(&mut self, xy: Pos, dir: Dir) {
+        if self.rocks[xy] != b'O' {
+            return;
+        }
+        self.rocks[xy] = b'.';
+        let mut xy = xy;
+        while let Some(next) = self.rocks.step(xy, dir).filter(|&next| self.rocks[next] == b'.') {
+            xy = next;
+        }
+        self.rocks[xy] = b'O';
+    }
     pub fn roll_north(&mut self) {
-        for y in 0..self.rocks.len() {
-            for x in 0..self.rocks[0].len() {
-                if self.rocks[y][x] == 'O' {
-                    self.rocks[y][x] = '.';
-                    let mut yy = y;
-                    while yy > 0 && self.rocks[yy - 1][x] == '.' {
-                        yy -= 1;
-                    }
-                    self.rocks[yy][x] = 'O';
-                }
+        for y in 0..self.rocks.height() {
+            for x in 0..self.rocks.width() {
+                self.roll((x, y), Dir::N);
             }
         }
     }
     pub fn roll_south(&mut self) {
-        for y in (0..self.rocks.len()).rev() {
-            for x in 0..self.rocks[0].len() {
-                if self.rocks[y][x] == 'O' {
-                    self.rocks[y][x] = '.';
-                    let mut yy = y;
-                    while yy < self.rocks.len() - 1 && self.rocks[yy + 1][x] == '.' {
-                        yy += 1;
-                    }
-                    self.rocks[yy][x] = 'O';
-                }
+        for y in (0..self.rocks.height()).rev() {
+            for x in 0..self.rocks.width() {
+                self.roll((x, y), Dir::S);
             }
         }
     }
     pub fn roll_east(&mut self) {
-        for x in (0..self.rocks[0].len()).rev() {
-            for y in 0..self.rocks.len() {
-                if self.rocks[y][x] == 'O' {
-                    self.rocks[y][x] = '.';
-                    let mut xx = x;
-                    while xx < self.rocks[0].len() - 1 && self.rocks[y][xx + 1] == '.' {
-                        xx += 1;
-                    }
-                    self.rocks[y][xx] = 'O';
-                }
+        for x in (0..self.rocks.width()).rev() {
+            for y in 0..self.rocks.height() {
+                self.roll((x, y), Dir::E);
             }
         }
     }
     pub fn roll_west(&mut self) {
-        for x in 0..self.rocks[0].len() {
-            for y in 0..self.rocks.len() {
-                if self.rocks[y][x] == 'O' {
-                    self.rocks[y][x] = '.';
-                    let mut xx = x;
-                    while xx > 0 && self.rocks[y][xx - 1] == '.' {
-                        xx -= 1;
-                    }
-                    self.rocks[y][xx] = 'O';
-                }
+        for x in 0..self.rocks.width() {
+            for y in 0..self.rocks.height() {
+                self.roll((x, y), Dir::W);
             }
         }
     }
@@ -75,38 +58,37 @@ impl Platform {
     }
     pub fn weight(&self) -> usize {
         self.rocks
-            .iter()
+            .rows()
             .enumerate()
-            .map(|(y, row)| (self.rocks.len() - y) * row.iter().filter(|c| c == &&'O').count())
+            .map(|(y, row)| (self.rocks.height() - y) * row.iter().filter(|c| c == &&b'O').count())
             .sum()
     }
+    /// Draw the platform on stderr, so that it stays out of the results
     fn print(&self) {
-        self.rocks.iter().for_each(|row| {
-            row.iter().for_each(|c| print!("{}", c));
-            println!();
+        self.rocks.rows().for_each(|row| {
+            eprintln!("{}", String::from_utf8_lossy(row));
         });
     }
 }
 
 pub struct Day14();
 
-impl Day14 {
-    pub fn parse(input: &[String]) -> Platform {
-        Platform {
-            rocks: input.iter().map(|line| line.chars().collect()).collect(),
-        }
-    }
-}
-
 type Solution = usize;
 impl DaySolver<Solution> for Day14 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let mut platform = Self::parse(&input);
+    type Parsed = Platform;
+
+    fn parse(input: &str) -> Result<Platform> {
+        Ok(Platform {
+            rocks: Grid::parse(input, |c| b"O#.".contains(&c).then_some(c))?,
+        })
+    }
+    fn part1(platform: &Platform) -> Result<Solution> {
+        let mut platform = platform.clone();
         platform.roll_north();
-        Some(platform.weight())
+        Ok(platform.weight())
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let mut platform = Self::parse(&input);
+    fn part2(platform: &Platform) -> Result<Solution> {
+        let mut platform = platform.clone();
         let mut record = HashMap::<Platform, u64>::new();
         let mut start: u64 = 0;
         let mut cycle: u64 = 0;
@@ -130,6 +112,6 @@ impl DaySolver<Solution> for Day14 {
         for _ in 0..x {
             origin.spin();
         }
-        Some(origin.weight())
+        Ok(origin.weight())
     }
 }
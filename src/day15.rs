@@ -1,7 +1,7 @@
 #![allow(unused)]
 use std::array;
 
-use crate::util::DaySolver;
+use crate::util::{AocError, DaySolver, Result};
 
 pub struct Day15();
 
@@ -38,15 +38,16 @@ impl HASHMAP {
         }
         self.boxes[index].push((hash, value));
     }
-    pub fn operate(&mut self, operation: &[u8]) {
-        if operation.last() == Some(&b'-') {
-            self.remove(&operation[0..operation.len() - 1]);
-        } else {
-            self.insert(
-                &operation[0..operation.len() - 2],
-                (operation.last().unwrap() - b'0') as u64,
-            );
+    /// Apply a step like `rn=1` or `cm-`, returning false if it is neither
+    pub fn operate(&mut self, operation: &[u8]) -> bool {
+        match operation {
+            [label @ .., b'-'] => self.remove(label),
+            [label @ .., b'=', digit] if digit.is_ascii_digit() => {
+                self.insert(label, (digit - b'0') as u64)
+            }
+            _ => return false,
         }
+        true
     }
     pub fn focusing_power(&self) -> u64 {
         self.boxes
@@ -79,29 +80,39 @@ impl Day15 {
             .map(|(i, c)| (2 << i) * c)
             .sum()
     }
-    pub fn part2_load(input: &[String]) -> HASHMAP {
+    fn arrange(sequence: &[u8]) -> Result<HASHMAP> {
         let mut hm = HASHMAP::default();
-        input[0]
-            .as_bytes()
-            .split(|c| c == &b',')
-            .for_each(|step| hm.operate(step));
-        hm
+        let mut column = 0;
+        for step in sequence.split(|c| c == &b',') {
+            if !hm.operate(step) {
+                return Err(AocError::parse(column, "expected a step like rn=1 or cm-"));
+            }
+            column += step.len() + 1;
+        }
+        Ok(hm)
     }
 }
 
 type Solution = u64;
 impl DaySolver<Solution> for Day15 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        Some(
-            input[0]
-                .as_bytes()
-                .split(|c| c == &b',')
-                .map(|step| Self::hash(0, step) as u64)
-                .sum(),
-        )
+    // The initialization sequence, which is one long line
+    type Parsed = Vec<u8>;
+
+    fn parse(input: &str) -> Result<Vec<u8>> {
+        input
+            .lines()
+            .next()
+            .map(|line| line.as_bytes().to_vec())
+            .ok_or_else(|| AocError::parse(0, "the input is empty"))
+    }
+    fn part1(sequence: &Vec<u8>) -> Result<Solution> {
+        Ok(sequence
+            .split(|c| c == &b',')
+            .map(|step| Self::hash(0, step) as u64)
+            .sum())
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let mut hm = Self::part2_load(&input);
-        Some(hm.focusing_power())
+    fn part2(sequence: &Vec<u8>) -> Result<Solution> {
+        let mut hm = Self::arrange(sequence)?;
+        Ok(hm.focusing_power())
     }
 }
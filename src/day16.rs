@@ -1,88 +1,58 @@
 #![allow(unused)]
-use crate::util::DaySolver;
-
-#[derive(Debug, Eq, PartialEq)]
-pub enum Dir {
-    N,
-    S,
-    E,
-    W,
-}
-
-impl Dir {
-    pub fn is_ns(&self) -> bool {
-        self == &Dir::N || self == &Dir::S
-    }
-    pub fn apply(&self, x: i32, y: i32) -> (i32, i32) {
-        match self {
-            Dir::N => (x, y - 1),
-            Dir::S => (x, y + 1),
-            Dir::E => (x + 1, y),
-            Dir::W => (x - 1, y),
-        }
-    }
-    pub fn index(&self) -> usize {
-        match self {
-            Dir::N => 0,
-            Dir::S => 1,
-            Dir::E => 2,
-            Dir::W => 3,
-        }
-    }
-}
+use crate::util::grid::{Dir, Grid, Pos};
+use crate::util::{AocError, DaySolver, Result};
 
 pub struct Visitations {
-    /// (NS, EW)
-    tiles: Vec<[bool; 4]>,
-    width: usize,
+    /// Which directions each tile has been crossed in
+    tiles: Grid<[bool; 4]>,
 }
 impl From<&Contraption> for Visitations {
     fn from(contraption: &Contraption) -> Self {
         Visitations {
-            tiles: vec![[false; 4]; contraption.width * contraption.height],
-            width: contraption.width,
+            tiles: Grid::new(
+                contraption.tiles.width(),
+                contraption.tiles.height(),
+                [false; 4],
+            ),
         }
     }
 }
 
 impl Visitations {
     pub fn visits(&self) -> usize {
-        self.tiles.iter().filter(|v| v.iter().any(|x| *x)).count()
+        self.tiles
+            .iter()
+            .filter(|(_, v)| v.iter().any(|x| *x))
+            .count()
     }
-    pub fn visit(&mut self, x: i32, y: i32, dir: &Dir) {
-        self.tiles[(y as usize * self.width) + x as usize][dir.index()] = true;
+    pub fn visit(&mut self, pos: Pos, dir: Dir) {
+        self.tiles[pos][dir.index()] = true;
     }
-    pub fn has_visited(&self, x: i32, y: i32, dir: &Dir) -> bool {
-        self.tiles[(y as usize * self.width) + x as usize][dir.index()]
+    pub fn has_visited(&self, pos: Pos, dir: Dir) -> bool {
+        self.tiles[pos][dir.index()]
     }
 }
 
 pub struct Contraption {
-    tiles: Vec<Vec<u8>>,
-    width: usize,
-    height: usize,
+    tiles: Grid<u8>,
 }
-impl From<&[String]> for Contraption {
-    fn from(value: &[String]) -> Self {
-        let tiles: Vec<Vec<u8>> = value.iter().map(|line| line.as_bytes().into()).collect();
-        Contraption {
-            width: tiles[0].len(),
-            height: tiles.len(),
-            tiles,
-        }
+impl TryFrom<&str> for Contraption {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        let tiles = Grid::parse(value, |c| b"./\\|-".contains(&c).then_some(c))?;
+        Ok(Contraption { tiles })
     }
 }
 impl Contraption {
-    pub fn get(&self, x: i32, y: i32) -> u8 {
-        self.tiles[y as usize][x as usize]
-    }
-    pub fn find_visits(&self, visitations: &mut Visitations, mut x: i32, mut y: i32, mut dir: Dir) {
-        while !(x < 0 || x >= self.tiles[0].len() as i32 || y < 0 || y >= self.tiles.len() as i32)
-            && !visitations.has_visited(x, y, &dir)
-        {
-            visitations.visit(x, y, &dir);
-            match self.get(x, y) {
-                b'.' => (x, y) = dir.apply(x, y),
+    pub fn find_visits(&self, visitations: &mut Visitations, mut pos: Option<Pos>, mut dir: Dir) {
+        while let Some(at) = pos {
+            if visitations.has_visited(at, dir) {
+                return;
+            }
+            visitations.visit(at, dir);
+            match self.tiles[at] {
+                b'.' => {}
                 b'/' => {
                     dir = match dir {
                         Dir::N => Dir::E,
@@ -90,7 +60,6 @@ impl Contraption {
                         Dir::E => Dir::N,
                         Dir::W => Dir::S,
                     };
-                    (x, y) = dir.apply(x, y);
                 }
                 b'\\' => {
                     dir = match dir {
@@ -99,33 +68,29 @@ impl Contraption {
                         Dir::E => Dir::S,
                         Dir::W => Dir::N,
                     };
-                    (x, y) = dir.apply(x, y);
                 }
                 b'|' => {
-                    if dir.is_ns() {
-                        (x, y) = dir.apply(x, y);
-                    } else {
-                        self.find_visits(visitations, x, y + 1, Dir::S);
-                        self.find_visits(visitations, x, y - 1, Dir::N);
+                    if !dir.is_vertical() {
+                        self.find_visits(visitations, self.tiles.step(at, Dir::S), Dir::S);
+                        self.find_visits(visitations, self.tiles.step(at, Dir::N), Dir::N);
                         return;
                     }
                 }
                 b'-' => {
-                    if !dir.is_ns() {
-                        (x, y) = dir.apply(x, y);
-                    } else {
-                        self.find_visits(visitations, x + 1, y, Dir::E);
-                        self.find_visits(visitations, x - 1, y, Dir::W);
+                    if dir.is_vertical() {
+                        self.find_visits(visitations, self.tiles.step(at, Dir::E), Dir::E);
+                        self.find_visits(visitations, self.tiles.step(at, Dir::W), Dir::W);
                         return;
                     }
                 }
-                _ => panic!(),
+                _ => unreachable!("the tiles were checked while parsing"),
             }
+            pos = self.tiles.step(at, dir);
         }
     }
-    pub fn visits(&self, x: usize, y: usize, dir: Dir) -> usize {
+    pub fn visits(&self, pos: Pos, dir: Dir) -> usize {
         let mut visitations = Visitations::from(self);
-        self.find_visits(&mut visitations, x as i32, y as i32, dir);
+        self.find_visits(&mut visitations, Some(pos), dir);
         visitations.visits()
     }
 }
@@ -133,30 +98,25 @@ impl Contraption {
 pub struct Day16();
 
 type Solution = usize;
-impl Day16 {
-    pub fn parse(input: &[String]) -> Contraption {
-        Contraption::from(input)
-    }
-}
-
 impl DaySolver<Solution> for Day16 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let contraption = Self::parse(&input);
-        let mut visitations = Visitations::from(&contraption);
-        contraption.find_visits(&mut visitations, 0, 0, Dir::E);
-        Some(visitations.visits())
+    type Parsed = Contraption;
+
+    fn parse(input: &str) -> Result<Contraption> {
+        Contraption::try_from(input)
+    }
+    fn part1(contraption: &Contraption) -> Result<Solution> {
+        Ok(contraption.visits((0, 0), Dir::E))
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let contraption = Self::parse(&input);
+    fn part2(contraption: &Contraption) -> Result<Solution> {
         let mut max: usize = 0;
-        for x in 0..contraption.width {
-            max = max.max(contraption.visits(x, 0, Dir::N));
-            max = max.max(contraption.visits(x, 0, Dir::S));
+        for x in 0..contraption.tiles.width() {
+            max = max.max(contraption.visits((x, 0), Dir::N));
+            max = max.max(contraption.visits((x, 0), Dir::S));
         }
-        for y in 0..contraption.height {
-            max = max.max(contraption.visits(0, y, Dir::E));
-            max = max.max(contraption.visits(0, y, Dir::W));
+        for y in 0..contraption.tiles.height() {
+            max = max.max(contraption.visits((0, y), Dir::E));
+            max = max.max(contraption.visits((0, y), Dir::W));
         }
-        Some(max)
+        Ok(max)
     }
 }
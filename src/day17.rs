@@ -2,68 +2,36 @@
 use std::collections::{BinaryHeap, HashMap};
 use std::hash::{Hash, Hasher};
 
-use crate::util::DaySolver;
+use crate::util::grid::{Dir, Grid, Pos, Turn};
+use crate::util::{AocError, DaySolver, Result};
 
 #[derive(Debug)]
 pub struct Weights {
-    blocks: Vec<Vec<usize>>,
-    width: i32,
-    height: i32,
+    blocks: Grid<usize>,
 }
-impl From<&[String]> for Weights {
-    fn from(value: &[String]) -> Self {
-        let blocks: Vec<Vec<usize>> = value
-            .iter()
-            .map(|row| row.chars().map(|c| (c as u8 - b'0') as usize).collect())
-            .collect();
-        Self {
-            width: blocks[0].len() as i32,
-            height: blocks.len() as i32,
-            blocks,
-        }
+impl TryFrom<&str> for Weights {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        let blocks = Grid::parse(value, |c| c.is_ascii_digit().then(|| (c - b'0') as usize))?;
+        Ok(Self { blocks })
     }
 }
 impl Weights {
-    pub fn get(&self, (x, y): (i32, i32)) -> Option<usize> {
-        if x < 0 || y < 0 || x >= self.width || y >= self.height {
-            // This won't matter, the next search step will also bounds check and fail
-            None
-        } else {
-            Some(self.blocks[y as usize][x as usize])
-        }
+    /// The block one step away, and how much heat is lost entering it
+    pub fn step(&self, xy: Pos, dir: Dir) -> Option<(Pos, usize)> {
+        let xy = self.blocks.step(xy, dir)?;
+        Some((xy, self.blocks[xy]))
     }
-}
-
-#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub enum Dir {
-    N,
-    S,
-    E,
-    W,
-}
-impl Dir {
-    pub fn step(&self, (x, y): (i32, i32)) -> (i32, i32) {
-        match self {
-            Dir::N => (x, y - 1),
-            Dir::S => (x, y + 1),
-            Dir::E => (x + 1, y),
-            Dir::W => (x - 1, y),
-        }
-    }
-    pub fn turns(&self) -> (Dir, Dir) {
-        match self {
-            Dir::N => (Dir::E, Dir::W),
-            Dir::S => (Dir::E, Dir::W),
-            Dir::E => (Dir::S, Dir::N),
-            Dir::W => (Dir::S, Dir::N),
-        }
+    pub fn goal(&self) -> Pos {
+        (self.blocks.width() - 1, self.blocks.height() - 1)
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct Prospect {
     distance: usize,
-    xy: (i32, i32),
+    xy: Pos,
     dir: Dir,
     consecutive: usize,
 }
@@ -110,7 +78,7 @@ impl Default for Distances {
 impl Distances {
     pub fn iterate1(&mut self, weights: &Weights) -> Option<usize> {
         let prospect = self.unvisited.pop().unwrap();
-        if prospect.xy == (weights.width - 1, weights.height - 1) {
+        if prospect.xy == weights.goal() {
             return Some(prospect.distance);
         }
         if let Some(prev_distance) = self.visited.get(&prospect) {
@@ -121,31 +89,19 @@ impl Distances {
             return None;
         }
         self.visited.insert(prospect.clone(), prospect.distance);
-        let (first_turn, second_turn) = prospect.dir.turns();
-        // First turn
-        let xy = first_turn.step(prospect.xy);
-        if let Some(weight) = weights.get(xy) {
-            self.unvisited.push(Prospect {
-                distance: prospect.distance + weight,
-                xy,
-                dir: first_turn,
-                consecutive: 1,
-            });
-        }
-        // Second turn
-        let xy = second_turn.step(prospect.xy);
-        if let Some(weight) = weights.get(xy) {
-            self.unvisited.push(Prospect {
-                distance: prospect.distance + weight,
-                xy,
-                dir: second_turn,
-                consecutive: 1,
-            });
+        for dir in [prospect.dir.turn(Turn::Left), prospect.dir.turn(Turn::Right)] {
+            if let Some((xy, weight)) = weights.step(prospect.xy, dir) {
+                self.unvisited.push(Prospect {
+                    distance: prospect.distance + weight,
+                    xy,
+                    dir,
+                    consecutive: 1,
+                });
+            }
         }
         // Straight ahead, if possible
         if prospect.consecutive < 3 {
-            let xy = prospect.dir.step(prospect.xy);
-            if let Some(weight) = weights.get(xy) {
+            if let Some((xy, weight)) = weights.step(prospect.xy, prospect.dir) {
                 self.unvisited.push(Prospect {
                     distance: prospect.distance + weight,
                     xy,
@@ -159,7 +115,7 @@ impl Distances {
     }
     pub fn iterate2(&mut self, weights: &Weights) -> Option<usize> {
         let prospect = self.unvisited.pop().unwrap();
-        if prospect.consecutive >= 4 && prospect.xy == (weights.width - 1, weights.height - 1) {
+        if prospect.consecutive >= 4 && prospect.xy == weights.goal() {
             return Some(prospect.distance);
         }
         if let Some(prev_distance) = self.visited.get(&prospect) {
@@ -170,55 +126,28 @@ impl Distances {
             return None;
         }
         self.visited.insert(prospect.clone(), prospect.distance);
-        // if prospect.consecutive >= 4 {
-        let (first_turn, second_turn) = prospect.dir.turns();
-        // First turn
-        let mut new_distance = prospect.distance;
-        let mut xy = prospect.xy;
-        let mut success = true;
-        for _ in 0..4 {
-            xy = first_turn.step(xy);
-            if let Some(weight) = weights.get(xy) {
-                new_distance += weight;
-            } else {
-                success = false;
-                break;
+        // Turning means going at least 4 blocks in the new direction
+        for dir in [prospect.dir.turn(Turn::Left), prospect.dir.turn(Turn::Right)] {
+            let mut new_distance = prospect.distance;
+            let mut xy = Some(prospect.xy);
+            for _ in 0..4 {
+                xy = xy.and_then(|xy| weights.step(xy, dir)).map(|(xy, weight)| {
+                    new_distance += weight;
+                    xy
+                });
             }
-        }
-        if success {
-            self.unvisited.push(Prospect {
-                distance: new_distance,
-                xy,
-                dir: first_turn,
-                consecutive: 4,
-            });
-        }
-        // Second turn
-        let mut new_distance = prospect.distance;
-        let mut xy = prospect.xy;
-        let mut success = true;
-        for _ in 0..4 {
-            xy = second_turn.step(xy);
-            if let Some(weight) = weights.get(xy) {
-                new_distance += weight;
-            } else {
-                success = false;
-                break;
+            if let Some(xy) = xy {
+                self.unvisited.push(Prospect {
+                    distance: new_distance,
+                    xy,
+                    dir,
+                    consecutive: 4,
+                });
             }
         }
-        if success {
-            self.unvisited.push(Prospect {
-                distance: new_distance,
-                xy,
-                dir: second_turn,
-                consecutive: 4,
-            });
-        }
-        // }
         // Straight ahead, if possible
         if prospect.consecutive < 10 {
-            let xy = prospect.dir.step(prospect.xy);
-            if let Some(weight) = weights.get(xy) {
+            if let Some((xy, weight)) = weights.step(prospect.xy, prospect.dir) {
                 self.unvisited.push(Prospect {
                     distance: prospect.distance + weight,
                     xy,
@@ -235,10 +164,7 @@ impl Distances {
 pub struct Day17();
 
 impl Day17 {
-    pub fn parse(input: &[String]) -> Weights {
-        Weights::from(input)
-    }
-    pub fn shortest_path_length1(weights: &Weights, dir: Dir) -> usize {
+    pub fn shortest_path_length1(weights: &Weights, dir: Dir) -> Result<usize> {
         let mut distances = Distances::default();
         distances.unvisited.push(Prospect {
             distance: 0,
@@ -246,14 +172,16 @@ impl Day17 {
             dir: Dir::E,
             consecutive: 1,
         });
-        loop {
+        while !distances.unvisited.is_empty() {
             if let Some(distance) = distances.iterate1(weights) {
-                return distance;
+                return Ok(distance);
             }
         }
-        unreachable!();
+        Err(AocError::solver(
+            "there is no path to the bottom right corner",
+        ))
     }
-    pub fn shortest_path_length2(weights: &Weights, dir: Dir) -> usize {
+    pub fn shortest_path_length2(weights: &Weights, dir: Dir) -> Result<usize> {
         let mut distances = Distances::default();
         distances.unvisited.push(Prospect {
             distance: 0,
@@ -261,23 +189,28 @@ impl Day17 {
             dir: Dir::E,
             consecutive: 1,
         });
-        loop {
+        while !distances.unvisited.is_empty() {
             if let Some(distance) = distances.iterate2(weights) {
-                return distance;
+                return Ok(distance);
             }
         }
-        unreachable!();
+        Err(AocError::solver(
+            "there is no path to the bottom right corner",
+        ))
     }
 }
 
 type Solution = usize;
 impl DaySolver<Solution> for Day17 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let weights = Self::parse(&input);
-        Some(Self::shortest_path_length1(&weights, Dir::E))
+    type Parsed = Weights;
+
+    fn parse(input: &str) -> Result<Weights> {
+        Weights::try_from(input)
+    }
+    fn part1(weights: &Weights) -> Result<Solution> {
+        Self::shortest_path_length1(weights, Dir::E)
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let weights = Self::parse(&input);
-        Some(Self::shortest_path_length2(&weights, Dir::E))
+    fn part2(weights: &Weights) -> Result<Solution> {
+        Self::shortest_path_length2(weights, Dir::E)
     }
 }
@@ -1,24 +1,17 @@
 #![allow(unused)]
 use std::collections::HashSet;
 
-use crate::util::DaySolver;
+use crate::util::grid::Dir;
+use crate::util::{column_of, parse_field, parse_lines, AocError, DaySolver, Result};
 
-#[derive(Debug, Eq, PartialEq)]
-pub enum Dir {
-    U,
-    D,
-    L,
-    R,
-}
-impl From<&str> for Dir {
-    fn from(value: &str) -> Self {
-        match value {
-            "U" => Dir::U,
-            "D" => Dir::D,
-            "L" => Dir::L,
-            "R" => Dir::R,
-            _ => unreachable!(),
-        }
+/// A direction as the dig plan writes it
+fn parse_dir(value: &str) -> Result<Dir> {
+    match value {
+        "U" => Ok(Dir::N),
+        "D" => Ok(Dir::S),
+        "L" => Ok(Dir::W),
+        "R" => Ok(Dir::E),
+        _ => Err(AocError::parse(0, format!("{value} is not a direction"))),
     }
 }
 
@@ -30,54 +23,72 @@ pub struct Step {
 
 pub struct Day18();
 
+/// The dig plan, read from the directions for part 1 and from the colors for part 2
+pub struct Plan {
+    steps: Vec<Step>,
+    color_steps: Vec<Step>,
+}
+
 impl Day18 {
-    pub fn parse1(input: &[String]) -> Vec<Step> {
-        input
-            .iter()
-            .map(|line| {
-                let dir = Dir::from(&line[..1]);
-                let distance = &line[2..4].trim_end().parse().unwrap();
-                let color = 0;
-                Step {
-                    dir,
-                    distance: *distance,
-                }
-            })
-            .collect()
+    /// Split a line like "R 6 (#70c710)" into its direction, distance, and color
+    fn fields(line: &str) -> Result<(&str, &str, &str)> {
+        let mut split = line.split(' ');
+        match (split.next(), split.next(), split.next(), split.next()) {
+            (Some(dir), Some(distance), Some(color), None) => Ok((dir, distance, color)),
+            _ => Err(AocError::parse(0, "expected '<dir> <distance> (#<color>)'")),
+        }
+    }
+    fn parse1(input: &str) -> Result<Vec<Step>> {
+        parse_lines(input, |line| {
+            let (dir, distance, _) = Self::fields(line)?;
+            let dir = parse_dir(dir)?;
+            let distance = parse_field(line, distance)?;
+            Ok(Step { dir, distance })
+        })
     }
-    pub fn parse2(input: &[String]) -> Vec<Step> {
-        input
-            .iter()
-            .map(|line| {
-                let dir = match &line[line.len() - 2..line.len() - 1].parse().unwrap() {
-                    0 => Dir::R,
-                    1 => Dir::D,
-                    2 => Dir::L,
-                    3 => Dir::U,
-                    _ => unreachable!(),
-                };
-                let distance =
-                    u64::from_str_radix(&line[line.len() - 7..line.len() - 2], 16).unwrap();
-                Step { dir, distance }
-            })
-            .collect()
+    fn parse2(input: &str) -> Result<Vec<Step>> {
+        parse_lines(input, |line| {
+            let (_, _, color) = Self::fields(line)?;
+            let hex = color
+                .strip_prefix("(#")
+                .and_then(|hex| hex.strip_suffix(')'))
+                .filter(|hex| hex.len() == 6 && hex.is_char_boundary(5))
+                .ok_or_else(|| {
+                    AocError::parse(column_of(line, color), "expected (#<6 hex digits>)")
+                })?;
+            let dir = match &hex[5..] {
+                "0" => Dir::E,
+                "1" => Dir::S,
+                "2" => Dir::W,
+                "3" => Dir::N,
+                other => {
+                    return Err(AocError::parse(
+                        column_of(line, other),
+                        format!("{other} is not a direction"),
+                    ))
+                }
+            };
+            let distance = u64::from_str_radix(&hex[..5], 16)
+                .map_err(|e| AocError::parse(column_of(line, hex), e))?;
+            Ok(Step { dir, distance })
+        })
     }
     pub fn area(steps: &[Step]) -> i64 {
         let mut x: i64 = 0;
         let mut area: i64 = 1;
         steps.iter().for_each(|step| {
             match step.dir {
-                Dir::U => {
+                Dir::N => {
                     area += step.distance as i64 * (x + 1);
                 }
-                Dir::D => {
+                Dir::S => {
                     area -= step.distance as i64 * x;
                 }
-                Dir::L => {
+                Dir::W => {
                     x += step.distance as i64;
                     area += step.distance as i64;
                 }
-                Dir::R => {
+                Dir::E => {
                     x -= step.distance as i64;
                 }
             };
@@ -88,12 +99,18 @@ impl Day18 {
 
 type Solution = i64;
 impl DaySolver<Solution> for Day18 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let mut steps = Self::parse1(&input);
-        Some(Self::area(&steps))
+    type Parsed = Plan;
+
+    fn parse(input: &str) -> Result<Plan> {
+        Ok(Plan {
+            steps: Self::parse1(input)?,
+            color_steps: Self::parse2(input)?,
+        })
+    }
+    fn part1(plan: &Plan) -> Result<Solution> {
+        Ok(Self::area(&plan.steps))
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let mut steps = Self::parse2(&input);
-        Some(Self::area(&steps))
+    fn part2(plan: &Plan) -> Result<Solution> {
+        Ok(Self::area(&plan.color_steps))
     }
 }
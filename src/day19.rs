@@ -1,7 +1,8 @@
 #![allow(unused)]
 use std::collections::HashMap;
 
-use crate::util::DaySolver;
+use crate::util::input::Input;
+use crate::util::{column_of, parse_field, AocError, DaySolver, Result};
 
 #[derive(Debug)]
 pub struct Workflow {
@@ -9,32 +10,45 @@ pub struct Workflow {
     rules: Vec<(usize, bool, u64, String)>,
     fallback: String,
 }
-impl From<&String> for Workflow {
-    fn from(value: &String) -> Self {
-        let (id, rules_str) = value.split_once('{').unwrap();
-        let rules_str = &rules_str[..rules_str.len() - 1];
-        let rules_vec: Vec<String> = rules_str.split(',').map(str::to_string).collect();
+impl TryFrom<&str> for Workflow {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        let (id, rules_str) = value
+            .split_once('{')
+            .ok_or_else(|| AocError::parse(0, "expected '<id>{<rules>}'"))?;
+        let rules_str = rules_str
+            .strip_suffix('}')
+            .ok_or_else(|| AocError::parse(value.len(), "expected a closing }"))?;
+        let rules_vec: Vec<&str> = rules_str.split(',').collect();
         let rules = rules_vec[..rules_vec.len() - 1]
             .iter()
             .map(|rule| {
-                let index = match rule.chars().next().unwrap() {
-                    'x' => 0,
-                    'm' => 1,
-                    'a' => 2,
-                    's' => 3,
-                    _ => unreachable!(),
+                let column = column_of(value, rule);
+                let index = match rule.chars().next() {
+                    Some('x') => 0,
+                    Some('m') => 1,
+                    Some('a') => 2,
+                    Some('s') => 3,
+                    _ => return Err(AocError::parse(column, "expected one of x, m, a, or s")),
+                };
+                let gt = match rule.chars().nth(1) {
+                    Some('>') => true,
+                    Some('<') => false,
+                    _ => return Err(AocError::parse(column + 1, "expected < or >")),
                 };
-                let gt = rule.chars().nth(1).unwrap() == '>';
-                let (cmp, next) = rule[2..].split_once(':').unwrap();
-                (index, gt, cmp.parse().unwrap(), next.to_string())
+                let (cmp, next) = rule[2..]
+                    .split_once(':')
+                    .ok_or_else(|| AocError::parse(column + 2, "expected '<number>:<id>'"))?;
+                Ok((index, gt, parse_field(value, cmp)?, next.to_string()))
             })
-            .collect();
-        let fallback = rules_vec.last().unwrap().to_string();
-        Workflow {
+            .collect::<Result<_>>()?;
+        let fallback = rules_vec[rules_vec.len() - 1].to_string();
+        Ok(Workflow {
             id: id.to_string(),
             rules,
             fallback,
-        }
+        })
     }
 }
 impl Workflow {
@@ -56,48 +70,49 @@ pub type Xmases = Vec<Xmas>;
 pub struct Day19();
 
 impl Day19 {
-    pub fn parse1(input: &[String]) -> (Workflows, Xmases) {
-        let split = input
-            .iter()
-            .enumerate()
-            .find(|(_, line)| line.is_empty())
-            .unwrap()
-            .0;
-        let workflows = input[0..split]
-            .iter()
-            .map(|line| {
-                let w = Workflow::from(line);
-                (w.id.to_string(), w)
-            })
-            .collect();
-        let xmases = input[split + 1..]
-            .iter()
-            .map(|line2| {
-                let mut iter = line2[1..line2.len() - 1].split(',');
-                let x = iter.next().unwrap()[2..].parse().unwrap();
-                let m = iter.next().unwrap()[2..].parse().unwrap();
-                let a = iter.next().unwrap()[2..].parse().unwrap();
-                let s = iter.next().unwrap()[2..].parse().unwrap();
-                [x, m, a, s]
+    /// Parse the paragraph of workflows, checking that every rule sends parts somewhere that exists
+    fn parse_workflows(input: Input) -> Result<Workflows> {
+        let workflows: Workflows = input
+            .numbered_lines()
+            .map(|(y, line)| {
+                let w = Workflow::try_from(line).map_err(|e| e.on_line(y))?;
+                Ok((w.id.to_string(), w))
             })
-            .collect();
-        (workflows, xmases)
+            .collect::<Result<_>>()?;
+        // Make sure every workflow can be followed, so that evaluation never gets lost
+        if !workflows.contains_key("in") {
+            return Err(AocError::parse(0, "there is no 'in' workflow"));
+        }
+        for (y, line) in input.numbered_lines() {
+            let workflow = &workflows[&line[..line.find('{').unwrap_or_default()]];
+            let targets = workflow.rules.iter().map(|rule| &rule.3);
+            for target in targets.chain([&workflow.fallback]) {
+                if target != "A" && target != "R" && !workflows.contains_key(target) {
+                    return Err(AocError::parse(
+                        line.find(target.as_str()).unwrap_or_default(),
+                        format!("there is no workflow {target}"),
+                    )
+                    .on_line(y));
+                }
+            }
+        }
+        Ok(workflows)
     }
-    pub fn parse2(input: &[String]) -> Workflows {
-        let split = input
-            .iter()
-            .enumerate()
-            .find(|(_, line)| line.is_empty())
-            .unwrap()
-            .0;
-        input
-            .iter()
-            .take(split)
-            .map(|line| {
-                let w = Workflow::from(line);
-                (w.id.to_string(), w)
-            })
-            .collect()
+    fn parse_xmas(line: &str) -> Result<Xmas> {
+        let fields = line
+            .strip_prefix('{')
+            .and_then(|line| line.strip_suffix('}'))
+            .ok_or_else(|| AocError::parse(0, "expected '{x=..,m=..,a=..,s=..}'"))?;
+        let mut xmas = [0; 4];
+        let mut iter = fields.split(',');
+        for (i, name) in ["x=", "m=", "a=", "s="].iter().enumerate() {
+            let field = iter.next().unwrap_or_default();
+            let value = field.strip_prefix(name).ok_or_else(|| {
+                AocError::parse(column_of(line, field), format!("expected {name}"))
+            })?;
+            xmas[i] = parse_field(line, value)?;
+        }
+        Ok(xmas)
     }
     pub fn eval(workflows: &Workflows, id: &str, xmas: &Xmas) -> bool {
         let mut id = id;
@@ -174,18 +189,29 @@ impl Day19 {
 
 type Solution = u64;
 impl DaySolver<Solution> for Day19 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let (workflows, xmases) = Self::parse1(&input);
-        Some(
-            xmases
-                .iter()
-                .filter(|xmas| Self::eval(&workflows, "in", xmas))
-                .map(|xmas| xmas.iter().sum::<u64>())
-                .sum(),
-        )
+    type Parsed = (Workflows, Xmases);
+
+    fn parse(input: &str) -> Result<(Workflows, Xmases)> {
+        let mut paragraphs = Input::new(input).paragraphs();
+        let workflows = Self::parse_workflows(
+            paragraphs
+                .next()
+                .ok_or_else(|| AocError::parse(0, "the input is empty"))?,
+        )?;
+        let xmases = paragraphs
+            .next()
+            .ok_or_else(|| AocError::parse(0, "expected a blank line after the workflows"))?
+            .parse_lines(Self::parse_xmas)?;
+        Ok((workflows, xmases))
+    }
+    fn part1((workflows, xmases): &(Workflows, Xmases)) -> Result<Solution> {
+        Ok(xmases
+            .iter()
+            .filter(|xmas| Self::eval(workflows, "in", xmas))
+            .map(|xmas| xmas.iter().sum::<u64>())
+            .sum())
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let workflows = Self::parse2(&input);
-        Some(Self::range_count(&workflows, "in", &[1; 4], &[4001; 4]))
+    fn part2((workflows, _): &(Workflows, Xmases)) -> Result<Solution> {
+        Ok(Self::range_count(workflows, "in", &[1; 4], &[4001; 4]))
     }
 }
@@ -1,79 +1,180 @@
 #![allow(unused)]
-use crate::util::DaySolver;
+use std::collections::BTreeMap;
+
+use crate::util::{column_of, parse_field, parse_lines, AocError, DaySolver, Result};
 
 type Solution = u32;
 
-struct Reveal {
-    r: u32,
-    g: u32,
-    b: u32,
+/// Some number of cubes of each color, like a handful that was revealed or the contents of a bag
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Cubes {
+    counts: BTreeMap<String, u32>,
 }
 
-impl From<&str> for Reveal {
-    fn from(value: &str) -> Self {
-        let mut reveal = Reveal { r: 0, g: 0, b: 0 };
-        for pull in value.split(',') {
-            let (digits, color) = pull.trim().split_once(' ').unwrap();
-            match color {
-                "red" => reveal.r = digits.parse().unwrap(),
-                "green" => reveal.g = digits.parse().unwrap(),
-                "blue" => reveal.b = digits.parse().unwrap(),
-                _ => panic!("{color} is not a valid color"),
-            }
+impl<S: Into<String>> FromIterator<(S, u32)> for Cubes {
+    /// Counts of the same color are added together
+    fn from_iter<I: IntoIterator<Item = (S, u32)>>(iter: I) -> Self {
+        let mut counts = BTreeMap::new();
+        for (color, count) in iter {
+            *counts.entry(color.into()).or_default() += count;
         }
-        reveal
+        Cubes { counts }
+    }
+}
+
+impl Cubes {
+    fn parse(line: &str, value: &str) -> Result<Self> {
+        value
+            .split(',')
+            .map(|pull| {
+                let pull = pull.trim();
+                let (digits, color) = pull.split_once(' ').ok_or_else(|| {
+                    AocError::parse(column_of(line, pull), "expected '<count> <color>'")
+                })?;
+                if color.is_empty() || !color.chars().all(char::is_alphabetic) {
+                    return Err(AocError::parse(
+                        column_of(line, color),
+                        format!("{color} is not a valid color"),
+                    ));
+                }
+                Ok((color, parse_field(line, digits)?))
+            })
+            .collect()
+    }
+    /// How many cubes there are of `color`, which is 0 for colors that aren't here at all
+    pub fn count(&self, color: &str) -> u32 {
+        self.counts.get(color).copied().unwrap_or_default()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.counts
+            .iter()
+            .map(|(color, count)| (color.as_str(), *count))
+    }
+    /// Whether a bag holding `bag` could have all of these cubes
+    pub fn fits_in(&self, bag: &Cubes) -> bool {
+        self.iter().all(|(color, count)| count <= bag.count(color))
+    }
+    /// The counts of `colors` multiplied together, so a color that isn't here makes it 0
+    pub fn power_over(&self, colors: &[&str]) -> u32 {
+        colors.iter().map(|color| self.count(color)).product()
     }
 }
 
-struct Game {
+/// A game where cubes were pulled out of a bag, shown, and put back a few times
+pub struct CubeGame {
     id: u32,
-    reveals: Vec<Reveal>,
+    reveals: Vec<Cubes>,
 }
 
-impl From<&String> for Game {
-    fn from(value: &String) -> Self {
-        let (game_id_str, reveals_str) = value.split_once(':').unwrap();
-        let id: u32 = game_id_str[5..].parse().unwrap();
-        // println!("{}", reveals_str);
-        let reveals = reveals_str.split(';').map(Reveal::from).collect();
-        Game { id, reveals }
+impl TryFrom<&str> for CubeGame {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        let (game_id_str, reveals_str) = value
+            .split_once(':')
+            .ok_or_else(|| AocError::parse(0, "expected 'Game <id>:'"))?;
+        let id_str = game_id_str
+            .strip_prefix("Game ")
+            .ok_or_else(|| AocError::parse(0, "expected 'Game <id>:'"))?;
+        let id: u32 = parse_field(value, id_str)?;
+        let reveals = reveals_str
+            .split(';')
+            .map(|reveal| Cubes::parse(value, reveal))
+            .collect::<Result<_>>()?;
+        Ok(CubeGame { id, reveals })
+    }
+}
+
+impl CubeGame {
+    pub fn new(id: u32, reveals: Vec<Cubes>) -> Self {
+        CubeGame { id, reveals }
+    }
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+    pub fn reveals(&self) -> &[Cubes] {
+        &self.reveals
+    }
+    /// Whether every reveal could have come out of `bag`
+    pub fn is_possible_with(&self, bag: &Cubes) -> bool {
+        self.reveals.iter().all(|reveal| reveal.fits_in(bag))
+    }
+    /// The reveals that show more cubes of some color than `bag` holds, with their index
+    pub fn ruling_out<'a>(&'a self, bag: &'a Cubes) -> impl Iterator<Item = (usize, &'a Cubes)> {
+        self.reveals
+            .iter()
+            .enumerate()
+            .filter(|(_, reveal)| !reveal.fits_in(bag))
+    }
+    /// The fewest cubes of each color that the game could have been played with
+    pub fn minimum_bag(&self) -> Cubes {
+        let mut bag = Cubes::default();
+        for (color, count) in self.reveals.iter().flat_map(Cubes::iter) {
+            let most = bag.counts.entry(color.to_string()).or_default();
+            *most = (*most).max(count);
+        }
+        bag
+    }
+    /// The power of the minimum bag over `colors`, which is 0 if one of them is never revealed
+    pub fn power_over(&self, colors: &[&str]) -> u32 {
+        self.minimum_bag().power_over(colors)
     }
 }
 
 pub struct Day2();
 
 impl Day2 {
-    fn parse(input: Vec<String>) -> Vec<Game> {
-        input.iter().map(Game::from).collect()
+    /// The bag that part 1 asks about
+    fn bag() -> Cubes {
+        Cubes::from_iter([("red", 12), ("green", 13), ("blue", 14)])
     }
 }
 
 impl DaySolver<Solution> for Day2 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let games = Self::parse(input);
-        Some(
-            games
-                .iter()
-                .filter(|g| !g.reveals.iter().any(|r| r.r > 12 || r.g > 13 || r.b > 14))
-                .map(|g| g.id)
-                .sum(),
-        )
-    }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let games = Self::parse(input);
-        Some(
-            games
-                .iter()
-                .map(|g| {
-                    let mut max = Reveal { r: 0, g: 0, b: 0 };
-                    for r in &g.reveals {
-                        max.r = max.r.max(r.r);
-                        max.g = max.g.max(r.g);
-                        max.b = max.b.max(r.b);
-                    }
-                    max.r * max.g * max.b
-                })
-                .sum(),
-        )
+    type Parsed = Vec<CubeGame>;
+
+    fn parse(input: &str) -> Result<Vec<CubeGame>> {
+        parse_lines(input, CubeGame::try_from)
+    }
+    fn part1(games: &Vec<CubeGame>) -> Result<Solution> {
+        let bag = Self::bag();
+        Ok(games
+            .iter()
+            .filter(|game| game.is_possible_with(&bag))
+            .map(CubeGame::id)
+            .sum())
+    }
+    fn part2(games: &Vec<CubeGame>) -> Result<Solution> {
+        let bag = Self::bag();
+        let colors: Vec<&str> = bag.iter().map(|(color, _)| color).collect();
+        Ok(games.iter().map(|game| game.power_over(&colors)).sum())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_more_colors() {
+        let game =
+            CubeGame::try_from("Game 7: 3 blue, 4 red; 2 purple, 6 blue; 1 red, 5 purple").unwrap();
+        assert_eq!(
+            game.minimum_bag(),
+            Cubes::from_iter([("blue", 6), ("red", 4), ("purple", 5)])
+        );
+        assert_eq!(game.power_over(&["blue", "red", "purple"]), 120);
+        assert_eq!(game.power_over(&["red", "green", "blue"]), 0);
+        let bag = Cubes::from_iter([("red", 12), ("green", 13), ("blue", 14)]);
+        assert!(!game.is_possible_with(&bag));
+        let ruled_out: Vec<usize> = game.ruling_out(&bag).map(|(i, _)| i).collect();
+        assert_eq!(ruled_out, vec![1, 2]);
+        assert!(CubeGame::try_from("Game 1: 3 blue, 4 r3d").is_err());
+    }
+
+    #[test]
+    fn test_missing_color() {
+        let games = Day2::parse("Game 1: 3 blue, 4 red; 1 red, 6 blue").unwrap();
+        assert_eq!(Day2::part2(&games).unwrap(), 0);
     }
 }
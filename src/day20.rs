@@ -6,12 +6,12 @@ use std::{
 
 use num::Integer;
 
-use crate::util::DaySolver;
+use crate::util::{parse_lines, AocError, DaySolver, Result};
 
 pub type Pulse = (u64, u64, bool);
 pub type Pulses = VecDeque<Pulse>;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Kind {
     Broadcaster,
     FlipFlop(bool),
@@ -19,35 +19,44 @@ pub enum Kind {
     Rx(bool),
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Module {
     id: u64,
     kind: Kind,
     outputs: Vec<u64>,
 }
 
-impl From<&String> for Module {
-    fn from(value: &String) -> Self {
-        let (id, outputs) = value.split_once(" -> ").unwrap();
+impl TryFrom<&str> for Module {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        let (id, outputs) = value
+            .split_once(" -> ")
+            .ok_or_else(|| AocError::parse(0, "expected '<module> -> <outputs>'"))?;
         let outputs = outputs.split(", ").map(Day20::hash).collect();
-        match id.chars().next().unwrap() {
-            'b' => Module {
+        Ok(match id.chars().next() {
+            Some('b') if id == "broadcaster" => Module {
                 id: Day20::hash("broadcaster"),
                 kind: Kind::Broadcaster,
                 outputs,
             },
-            '%' => Module {
+            Some('%') => Module {
                 id: Day20::hash(&id[1..]),
                 kind: Kind::FlipFlop(false),
                 outputs,
             },
-            '&' => Module {
+            Some('&') => Module {
                 id: Day20::hash(&id[1..]),
                 kind: Kind::Conjunction(HashMap::new()),
                 outputs,
             },
-            _ => unreachable!(),
-        }
+            _ => {
+                return Err(AocError::parse(
+                    0,
+                    "expected the broadcaster, a %flip-flop, or a &conjunction",
+                ))
+            }
+        })
     }
 }
 
@@ -91,8 +100,9 @@ impl Day20 {
         s.hash(&mut hasher);
         hasher.finish()
     }
-    pub fn parse1(input: &[String]) -> Modules {
-        let mut modules: Modules = input.iter().map(Module::from).map(|m| (m.id, m)).collect();
+    /// Wire up the modules, remembering the inputs of each conjunction
+    fn connect<'a>(modules: impl Iterator<Item = &'a Module>) -> Modules {
+        let mut modules: Modules = modules.map(|m| (m.id, m.clone())).collect();
         // Manually inject the rx module
         modules.insert(
             Self::hash("rx"),
@@ -117,38 +127,6 @@ impl Day20 {
         });
         modules
     }
-    pub fn parse2(input: &[String], without: &[u64]) -> Modules {
-        let mut modules: Modules = input
-            .iter()
-            // .filter(|line| without.iter().all(|w| !line.starts_with(&format!("&{w}"))))
-            .map(Module::from)
-            .filter(|m| !without.contains(&m.id))
-            .map(|m| (m.id, m))
-            .collect();
-        // Manually inject the rx module
-        modules.insert(
-            Day20::hash("rx"),
-            Module {
-                id: Day20::hash("rx"),
-                kind: Kind::Rx(false),
-                outputs: vec![],
-            },
-        );
-        let output_map: Vec<(u64, Vec<u64>)> = modules
-            .iter()
-            .map(|(id, module)| (*id, module.outputs.clone()))
-            .collect();
-        output_map.iter().for_each(|(id, outputs)| {
-            outputs.iter().for_each(|output| {
-                if let Some(module) = modules.get_mut(output) {
-                    if let Kind::Conjunction(inputs) = &mut module.kind {
-                        inputs.insert(*id, false);
-                    }
-                }
-            })
-        });
-        modules
-    }
     pub fn count_pulses(modules: &mut Modules) -> (u64, u64) {
         let mut lows = 0;
         let mut highs = 0;
@@ -170,17 +148,22 @@ impl Day20 {
 
 type Solution = u64;
 impl DaySolver<Solution> for Day20 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let mut modules = Self::parse1(&input);
+    type Parsed = Vec<Module>;
+
+    fn parse(input: &str) -> Result<Vec<Module>> {
+        parse_lines(input, Module::try_from)
+    }
+    fn part1(modules: &Vec<Module>) -> Result<Solution> {
+        let mut modules = Self::connect(modules.iter());
         let (mut lows, mut highs) = (0, 0);
         for _ in 0..1000 {
             let (l, h) = Self::count_pulses(&mut modules);
             lows += l;
             highs += h;
         }
-        Some(lows * highs)
+        Ok(lows * highs)
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
+    fn part2(parsed: &Vec<Module>) -> Result<Solution> {
         // Handy for debugging with https://csacademy.com/app/graph_editor/
         // for module in modules.values() {
         //     for output in module.outputs.iter() {
@@ -195,11 +178,11 @@ impl DaySolver<Solution> for Day20 {
         // cycles.
         // We abuse this by identifying the exits of each node, deactivating all but one, and
         // running until rx is triggered.
-        let mut modules = Self::parse1(&input);
+        let mut modules = Self::connect(parsed.iter());
         let hub = modules
             .values()
             .find(|module| module.outputs.iter().any(|o| *o == Self::hash("rx")))
-            .unwrap();
+            .ok_or_else(|| AocError::solver("nothing sends pulses to rx"))?;
         if let Kind::Conjunction(inputs) = &hub.kind {
             let mut lcm = 1;
             for chokepoint in inputs.keys() {
@@ -208,7 +191,8 @@ impl DaySolver<Solution> for Day20 {
                     .filter(|s| s != &chokepoint)
                     .copied()
                     .collect();
-                let mut modified_modules = Self::parse2(&input, &without);
+                let mut modified_modules =
+                    Self::connect(parsed.iter().filter(|m| !without.contains(&m.id)));
                 let mut count = 0;
                 while modified_modules.get(&Self::hash("rx")).unwrap().kind == Kind::Rx(false) {
                     Self::count_pulses(&mut modified_modules);
@@ -216,9 +200,11 @@ impl DaySolver<Solution> for Day20 {
                 }
                 lcm = lcm.lcm(&count);
             }
-            Some(lcm)
+            Ok(lcm)
         } else {
-            unreachable!()
+            Err(AocError::solver(
+                "the module sending pulses to rx is not a conjunction",
+            ))
         }
     }
 }
@@ -1,7 +1,9 @@
 #![allow(unused)]
 use std::collections::{HashMap, HashSet};
 
-use crate::util::DaySolver;
+use crate::util::grid::{Dir, Grid};
+use crate::util::input::Input;
+use crate::util::{AocError, DaySolver, Result};
 
 #[derive(Clone, Eq, PartialEq)]
 pub enum Tile {
@@ -10,33 +12,26 @@ pub enum Tile {
 }
 #[derive(Clone)]
 pub struct Field {
-    tiles: HashMap<(i32, i32), Tile>,
-    width: i32,
-    height: i32,
-    start: (i32, i32),
+    tiles: Grid<Tile>,
+    start: (isize, isize),
 }
 impl Field {
-    pub fn is_plot(&self, x: i32, y: i32) -> bool {
-        let x_mod = ((x % self.width) + self.width) % self.width;
-        let y_mod = ((y % self.height) + self.height) % self.height;
-        self.tiles.get(&(x_mod, y_mod)) == Some(&Tile::Plot)
+    /// The garden repeats infinitely in every direction
+    pub fn is_plot(&self, xy: (isize, isize)) -> bool {
+        self.tiles.get_wrapping(xy) == &Tile::Plot
     }
+    /// Draw the plots around the field on stderr, so that they stay out of the results
     pub fn print(&self, even: bool) {
-        for y in -self.height..self.height * 2 {
-            for x in -self.width..self.width * 2 {
-                match self.tiles.get(&(x, y)) {
-                    Some(Tile::Rock) => print!("#"),
-                    Some(Tile::Plot) => {
-                        if even == ((x + y) % 2 == 0) {
-                            print!("O");
-                        } else {
-                            print!(".");
-                        }
-                    }
-                    _ => print!(" "),
+        let (width, height) = (self.tiles.width() as isize, self.tiles.height() as isize);
+        for y in -height..height * 2 {
+            for x in -width..width * 2 {
+                match self.is_plot((x, y)) {
+                    false => eprint!("#"),
+                    true if even == ((x + y) % 2 == 0) => eprint!("O"),
+                    true => eprint!("."),
                 }
             }
-            println!();
+            eprintln!();
         }
     }
     pub fn count(&self, steps: i32) -> u64 {
@@ -45,23 +40,15 @@ impl Field {
         let mut just_visited = HashSet::new();
         for _ in 0..=steps {
             let mut next_visits = HashSet::new();
-            for (x, y) in to_visit.iter() {
-                let (x, y) = (*x, *y);
-                if self.is_plot(x, y) {
-                    if (((x + y) % 2) + 2) % 2 == steps % 2 {
+            for &(x, y) in to_visit.iter() {
+                if self.is_plot((x, y)) {
+                    if (x + y).rem_euclid(2) == steps as isize % 2 {
                         count += 1;
                     }
-                    if !just_visited.contains(&(x + 1, y)) {
-                        next_visits.insert((x + 1, y));
-                    }
-                    if !just_visited.contains(&(x - 1, y)) {
-                        next_visits.insert((x - 1, y));
-                    }
-                    if !just_visited.contains(&(x, y + 1)) {
-                        next_visits.insert((x, y + 1));
-                    }
-                    if !just_visited.contains(&(x, y - 1)) {
-                        next_visits.insert((x, y - 1));
+                    for next in Dir::ALL.map(|dir| dir.step((x, y))) {
+                        if !just_visited.contains(&next) {
+                            next_visits.insert(next);
+                        }
                     }
                 }
             }
@@ -75,44 +62,27 @@ impl Field {
 
 pub struct Day21();
 
-impl Day21 {
-    pub fn parse(input: &[String]) -> Field {
-        let (sy, line) = input
-            .iter()
-            .enumerate()
-            .find(|(_, line)| line.contains('S'))
-            .unwrap();
-        let sx = line.chars().enumerate().find(|(_, c)| c == &'S').unwrap().0;
-        let start = (sx as i32, sy as i32);
-        let tiles = input
-            .iter()
-            .enumerate()
-            .flat_map(|(y, line)| {
-                line.chars().enumerate().map(move |(x, c)| {
-                    (
-                        (x as i32, y as i32),
-                        match c {
-                            'S' | '.' => Tile::Plot,
-                            '#' => Tile::Rock,
-                            _ => unreachable!(),
-                        },
-                    )
-                })
-            })
-            .collect();
-        Field {
-            tiles,
-            width: input[0].len() as i32,
-            height: input.len() as i32,
-            start,
-        }
-    }
-}
-
 type Solution = u64;
 impl DaySolver<Solution> for Day21 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let mut field = Self::parse(&input);
+    type Parsed = Field;
+
+    fn parse(input: &str) -> Result<Field> {
+        let view = Input::new(input).grid()?;
+        let (x, y) = view
+            .position(b'S')
+            .ok_or_else(|| AocError::parse(0, "there is no S to start from"))?;
+        let tiles = Grid::from_view(&view, |c| match c {
+            b'S' | b'.' => Some(Tile::Plot),
+            b'#' => Some(Tile::Rock),
+            _ => None,
+        })?;
+        Ok(Field {
+            tiles,
+            start: (x as isize, y as isize),
+        })
+    }
+    fn part1(field: &Field) -> Result<Solution> {
+        let mut field = field.clone();
         // These will eventually pass with the test input
         // assert_eq!(field.count(field.start, 6), 16);
         // assert_eq!(field.count(field.start, 10), 50);
@@ -121,11 +91,10 @@ impl DaySolver<Solution> for Day21 {
         // assert_eq!(field.count(field.start, 500), 167004);
         // assert_eq!(field.count(field.start, 1000), 668697);
         // assert_eq!(field.count(field.start, 5000), 16733044);
-        Some(field.count(64))
+        Ok(field.count(64))
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let field = Self::parse(&input);
-        let w = field.width;
+    fn part2(field: &Field) -> Result<Solution> {
+        let w = field.tiles.width() as i32;
         let w2 = w / 2;
         let all_steps = 26501365;
         let a = field.count(w2);
@@ -139,6 +108,6 @@ impl DaySolver<Solution> for Day21 {
             res += r;
             r += d;
         }
-        Some(res)
+        Ok(res)
     }
 }
@@ -1,12 +1,12 @@
 #![allow(unused)]
-use crate::util::DaySolver;
+use crate::util::{column_of, parse_field, parse_lines, AocError, DaySolver, Result};
 
 use std::{
     cmp::Ordering,
     collections::{HashMap, HashSet},
 };
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Brick {
     x1: usize,
     x2: usize,
@@ -17,17 +17,38 @@ pub struct Brick {
     supports: HashSet<usize>,
     supported_by: HashSet<usize>,
 }
-impl From<&String> for Brick {
-    fn from(value: &String) -> Self {
+impl TryFrom<&str> for Brick {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        let (end1, end2) = value
+            .split_once('~')
+            .ok_or_else(|| AocError::parse(0, "expected '<x>,<y>,<z>~<x>,<y>,<z>'"))?;
+        let coords = |end: &str| -> Result<(usize, usize, u64)> {
+            let mut split = end.split(',');
+            let mut next = || {
+                split.next().ok_or_else(|| {
+                    AocError::parse(
+                        column_of(value, end) + end.len(),
+                        "expected three coordinates",
+                    )
+                })
+            };
+            let (x, y, z) = (next()?, next()?, next()?);
+            let (x, y): (usize, usize) = (parse_field(value, x)?, parse_field(value, y)?);
+            // The height map only covers a 10x10 area
+            if x >= 10 || y >= 10 {
+                return Err(AocError::parse(
+                    column_of(value, end),
+                    "x and y must be below 10",
+                ));
+            }
+            Ok((x, y, parse_field(value, z)?))
+        };
         // Conveniently, the coordinates are sorted, so x1 <= x2
-        let x1 = value[0..1].parse().unwrap();
-        let y1 = value[2..3].parse().unwrap();
-        let (z1, value) = value[4..].split_once('~').unwrap();
-        let z1 = z1.parse().unwrap();
-        let x2 = value[0..1].parse().unwrap();
-        let y2 = value[2..3].parse().unwrap();
-        let z2 = value[4..].parse().unwrap();
-        Brick {
+        let (x1, y1, z1) = coords(end1)?;
+        let (x2, y2, z2) = coords(end2)?;
+        Ok(Brick {
             x1,
             x2,
             y1,
@@ -36,7 +57,7 @@ impl From<&String> for Brick {
             z2,
             supports: HashSet::new(),
             supported_by: HashSet::new(),
-        }
+        })
     }
 }
 
@@ -101,35 +122,32 @@ impl Default for HeightMap {
 
 pub struct Day22();
 
-impl Day22 {
-    pub fn parse(input: &[String]) -> Vec<Brick> {
-        let mut bricks: Vec<Brick> = input.iter().map(Brick::from).collect();
-        bricks.sort_by_key(|b| b.z1);
-        bricks
-    }
-}
-
 type Solution = usize;
 impl DaySolver<Solution> for Day22 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let mut bricks = Self::parse(&input);
+    type Parsed = Vec<Brick>;
+
+    fn parse(input: &str) -> Result<Vec<Brick>> {
+        let mut bricks = parse_lines(input, Brick::try_from)?;
+        bricks.sort_by_key(|b| b.z1);
+        Ok(bricks)
+    }
+    fn part1(bricks: &Vec<Brick>) -> Result<Solution> {
+        let mut bricks = bricks.clone();
         let mut heights = HeightMap::new();
         heights.settle(&mut bricks);
         let mut count = 0;
-        Some(
-            bricks
-                .iter()
-                .filter(|brick| {
-                    brick
-                        .supports
-                        .iter()
-                        .all(|index| bricks[*index].supported_by.len() >= 2)
-                })
-                .count(),
-        )
+        Ok(bricks
+            .iter()
+            .filter(|brick| {
+                brick
+                    .supports
+                    .iter()
+                    .all(|index| bricks[*index].supported_by.len() >= 2)
+            })
+            .count())
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let mut bricks = Self::parse(&input);
+    fn part2(bricks: &Vec<Brick>) -> Result<Solution> {
+        let mut bricks = bricks.clone();
         let mut heights = HeightMap::new();
         heights.settle(&mut bricks);
         let mut sum = 0;
@@ -157,6 +175,6 @@ impl DaySolver<Solution> for Day22 {
             // println!("{knockouts:?}");
             sum += knockouts.len() - 1; // don't count the original brick
         }
-        Some(sum)
+        Ok(sum)
     }
 }
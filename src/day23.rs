@@ -1,16 +1,24 @@
 #![allow(unused)]
 use std::collections::{HashMap, HashSet};
 
-use crate::util::DaySolver;
+use crate::util::grid::{Dir, Grid, Pos};
+use crate::util::{AocError, DaySolver, Result};
 
 pub struct Trail {
-    tiles: Vec<Vec<char>>,
-    start: (usize, usize),
-    finish: (usize, usize),
+    tiles: Grid<u8>,
+    start: Pos,
+    finish: Pos,
 }
 impl Trail {
-    pub fn get(&self, (x, y): (usize, usize)) -> char {
-        self.tiles[y][x]
+    pub fn get(&self, xy: Pos) -> u8 {
+        self.tiles.get(xy).copied().unwrap_or(b'#')
+    }
+    /// The neighbors of `xy` that aren't forest
+    pub fn paths(&self, xy: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.tiles
+            .neighbors(xy)
+            .map(|(_, next)| next)
+            .filter(|&next| self.tiles[next] != b'#')
     }
 }
 
@@ -21,14 +29,6 @@ pub type Graph = HashMap<(usize, usize), GraphNode>;
 pub struct Day23();
 
 impl Day23 {
-    pub fn parse(input: &[String]) -> Trail {
-        let tiles: Vec<Vec<char>> = input.iter().map(|line| line.chars().collect()).collect();
-        Trail {
-            start: (1, 0),
-            finish: (tiles[0].len() - 2, tiles.len() - 1),
-            tiles,
-        }
-    }
     pub fn longest_path_given(
         trail: &Trail,
         (x, y): (usize, usize),
@@ -40,17 +40,20 @@ impl Day23 {
             return 1;
         }
         visited.insert((x, y));
+        let mut walk = |dir| {
+            trail
+                .tiles
+                .step((x, y), dir)
+                .map_or(0, |next| Self::longest_path_given(trail, next, visited))
+        };
         let longest_path = match trail.get((x, y)) {
-            '#' => 0,
-            '>' => Self::longest_path_given(trail, (x + 1, y), visited),
-            '<' => Self::longest_path_given(trail, (x - 1, y), visited), // TODO does not exist?
-            'v' => Self::longest_path_given(trail, (x, y + 1), visited),
-            '^' => Self::longest_path_given(trail, (x, y - 1), visited), // TODO does not exist?
-            '.' => Self::longest_path_given(trail, (x + 1, y), visited)
-                .max(Self::longest_path_given(trail, (x - 1, y), visited))
-                .max(Self::longest_path_given(trail, (x, y + 1), visited))
-                .max(Self::longest_path_given(trail, (x, y - 1), visited)),
-            _ => unreachable!(),
+            b'#' => 0,
+            b'>' => walk(Dir::E),
+            b'<' => walk(Dir::W), // TODO does not exist?
+            b'v' => walk(Dir::S),
+            b'^' => walk(Dir::N), // TODO does not exist?
+            b'.' => Dir::ALL.into_iter().map(walk).max().unwrap_or_default(),
+            _ => unreachable!("the tiles were checked while parsing"),
         };
         visited.remove(&(x, y));
         longest_path + 1
@@ -64,31 +67,15 @@ impl Day23 {
         let mut steps = 0;
         while (x, y) != trail.finish && (x, y) != trail.start {
             steps += 1;
-            let right = (x + 1, y) != last && trail.get((x + 1, y)) != '#';
-            let left = (x - 1, y) != last && trail.get((x - 1, y)) != '#';
-            let down = (x, y + 1) != last && trail.get((x, y + 1)) != '#';
-            let up = (x, y - 1) != last && trail.get((x, y - 1)) != '#';
-            if (right && !left && !down && !up) {
-                last = (x, y);
-                (x, y) = (x + 1, y);
-                continue;
-            }
-            if (!right && left && !down && !up) {
-                last = (x, y);
-                (x, y) = (x - 1, y);
-                continue;
-            }
-            if (!right && !left && down && !up) {
-                last = (x, y);
-                (x, y) = (x, y + 1);
-                continue;
-            }
-            if (!right && !left && !down && up) {
-                last = (x, y);
-                (x, y) = (x, y - 1);
-                continue;
+            // Keep going as long as there is only one way forward
+            let mut forward = trail.paths((x, y)).filter(|&next| next != last);
+            match (forward.next(), forward.next()) {
+                (Some(next), None) => {
+                    last = (x, y);
+                    (x, y) = next;
+                }
+                _ => break,
             }
-            break;
         }
         (steps, (x, y))
     }
@@ -104,29 +91,8 @@ impl Day23 {
                 continue;
             }
             let mut edges = vec![];
-            if trail.get((x + 1, y)) != '#' {
-                let edge = Self::follow_path(trail, (x, y), (x + 1, y));
-                if !nodes_to_check.contains(&edge.1) && !graph.contains_key(&edge.1) {
-                    nodes_to_check.push(edge.1);
-                }
-                edges.push(edge);
-            }
-            if trail.get((x - 1, y)) != '#' {
-                let edge = Self::follow_path(trail, (x, y), (x - 1, y));
-                if !nodes_to_check.contains(&edge.1) && !graph.contains_key(&edge.1) {
-                    nodes_to_check.push(edge.1);
-                }
-                edges.push(edge);
-            }
-            if trail.get((x, y + 1)) != '#' {
-                let edge = Self::follow_path(trail, (x, y), (x, y + 1));
-                if !nodes_to_check.contains(&edge.1) && !graph.contains_key(&edge.1) {
-                    nodes_to_check.push(edge.1);
-                }
-                edges.push(edge);
-            }
-            if trail.get((x, y - 1)) != '#' {
-                let edge = Self::follow_path(trail, (x, y), (x, y - 1));
+            for next in trail.paths((x, y)) {
+                let edge = Self::follow_path(trail, (x, y), next);
                 if !nodes_to_check.contains(&edge.1) && !graph.contains_key(&edge.1) {
                     nodes_to_check.push(edge.1);
                 }
@@ -164,20 +130,33 @@ impl Day23 {
         visited.pop();
         distance
     }
-    pub fn longest_path_in_graph(trail: &Trail, graph: &Graph) -> usize {
-        Self::longest_path_in_graph_given(trail, graph, trail.start, &mut vec![]).unwrap() + 1
+    pub fn longest_path_in_graph(trail: &Trail, graph: &Graph) -> Result<usize> {
+        Self::longest_path_in_graph_given(trail, graph, trail.start, &mut vec![])
+            .map(|distance| distance + 1)
+            .ok_or_else(|| AocError::solver("there is no path to the finish"))
     }
 }
 
 type Solution = usize;
 impl DaySolver<Solution> for Day23 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let trail = Self::parse(&input);
-        Some(Self::longest_path(&trail))
+    type Parsed = Trail;
+
+    fn parse(input: &str) -> Result<Trail> {
+        let tiles = Grid::parse(input, |c| b"#.<>^v".contains(&c).then_some(c))?;
+        if tiles.width() < 3 || tiles.height() < 3 {
+            return Err(AocError::parse(0, "the trail is too small"));
+        }
+        Ok(Trail {
+            start: (1, 0),
+            finish: (tiles.width() - 2, tiles.height() - 1),
+            tiles,
+        })
+    }
+    fn part1(trail: &Trail) -> Result<Solution> {
+        Ok(Self::longest_path(trail))
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let trail = Self::parse(&input);
-        let graph = Self::build_graph(&trail);
-        Some(Self::longest_path_in_graph(&trail, &graph))
+    fn part2(trail: &Trail) -> Result<Solution> {
+        let graph = Self::build_graph(trail);
+        Self::longest_path_in_graph(trail, &graph)
     }
 }
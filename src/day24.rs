@@ -1,5 +1,5 @@
 #![allow(unused)]
-use crate::util::DaySolver;
+use crate::util::{column_of, parse_field, parse_lines, AocError, DaySolver, Result};
 
 use num::{BigInt, BigRational, Integer, Zero};
 use std::str::FromStr;
@@ -14,25 +14,34 @@ pub struct Hail {
     vz: BigInt,
 }
 
-impl From<&String> for Hail {
-    fn from(value: &String) -> Self {
-        let (coords, velocities) = value.split_once(" @ ").unwrap();
-        let mut c_iter = coords.split(", ");
-        let x = BigInt::from_str(c_iter.next().unwrap()).unwrap();
-        let y = BigInt::from_str(c_iter.next().unwrap()).unwrap();
-        let z = BigInt::from_str(c_iter.next().unwrap()).unwrap();
-        let mut v_iter = velocities.split(", ");
-        let vx = BigInt::from_str(v_iter.next().unwrap()).unwrap();
-        let vy = BigInt::from_str(v_iter.next().unwrap()).unwrap();
-        let vz = BigInt::from_str(v_iter.next().unwrap()).unwrap();
-        Hail {
+impl TryFrom<&str> for Hail {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        let (coords, velocities) = value
+            .split_once(" @ ")
+            .ok_or_else(|| AocError::parse(0, "expected '<position> @ <velocity>'"))?;
+        let triple = |field: &str| -> Result<[BigInt; 3]> {
+            let mut iter = field.split(", ");
+            let mut next = || match iter.next() {
+                Some(n) => parse_field::<BigInt>(value, n.trim_start()),
+                None => Err(AocError::parse(
+                    column_of(value, field) + field.len(),
+                    "expected three numbers",
+                )),
+            };
+            Ok([next()?, next()?, next()?])
+        };
+        let [x, y, z] = triple(coords)?;
+        let [vx, vy, vz] = triple(velocities)?;
+        Ok(Hail {
             x,
             y,
             z,
             vx,
             vy,
             vz,
-        }
+        })
     }
 }
 
@@ -82,12 +91,6 @@ impl Hail {
 }
 
 pub struct Day24();
-impl Day24 {
-    pub fn parse(input: &[String]) -> Vec<Hail> {
-        input.iter().map(Hail::from).collect()
-    }
-}
-
 impl Day24 {
     fn find_x(vx: &BigInt, vy: &BigInt, a: &Hail, b: &Hail) -> Option<BigInt> {
         let xa = &a.x;
@@ -157,8 +160,12 @@ fn float_it(n: &BigInt) -> f64 {
 
 type Solution = u64;
 impl DaySolver<Solution> for Day24 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let hails = Self::parse(&input);
+    type Parsed = Vec<Hail>;
+
+    fn parse(input: &str) -> Result<Vec<Hail>> {
+        parse_lines(input, Hail::try_from)
+    }
+    fn part1(hails: &Vec<Hail>) -> Result<Solution> {
         let zero = BigRational::new(BigInt::from(0), BigInt::from(1));
         let min = BigRational::new(BigInt::from(200000000000000_u64), BigInt::from(1));
         let max = BigRational::new(BigInt::from(400000000000000_u64), BigInt::from(1));
@@ -175,10 +182,12 @@ impl DaySolver<Solution> for Day24 {
                 }
             }
         }
-        Some(sum)
+        Ok(sum)
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let hails = Self::parse(&input);
+    fn part2(hails: &Vec<Hail>) -> Result<Solution> {
+        if hails.len() < 2 {
+            return Err(AocError::solver("at least two hailstones are needed"));
+        }
         let min_v = -500;
         let max_v = 500;
         for vx in min_v..max_v {
@@ -190,14 +199,16 @@ impl DaySolver<Solution> for Day24 {
                         for vz in min_v..max_v {
                             let vz = BigInt::from(vz);
                             let rock = Self::solve(&vx, &vy, &vz, &hails[0], &t);
-                            if Self::verify_solution(&rock, &hails) {
-                                return Some((&rock.x + &rock.y + &rock.z).to_u64_digits().1[0]);
+                            if Self::verify_solution(&rock, hails) {
+                                return Ok((&rock.x + &rock.y + &rock.z).to_u64_digits().1[0]);
                             }
                         }
                     }
                 }
             }
         }
-        unreachable!()
+        Err(AocError::solver(format!(
+            "no rock velocity between {min_v} and {max_v} hits every hailstone"
+        )))
     }
 }
@@ -4,7 +4,7 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-use crate::util::DaySolver;
+use crate::util::{AocError, DaySolver, Result};
 
 pub struct Graph {
     nodes: HashMap<u64, Vec<u64>>,
@@ -22,7 +22,7 @@ impl Graph {
         start: u64,
         ends: &[u64],
         excluding: &[Edge],
-    ) -> Result<Vec<Edge>, usize> {
+    ) -> std::result::Result<Vec<Edge>, usize> {
         let mut to_visit: VecDeque<u64> = VecDeque::from([start]);
         let mut to_visit_set: HashSet<u64> = HashSet::new();
         // let mut visited = HashSet::new();
@@ -60,17 +60,29 @@ pub fn is_edge_in(edges: &[Edge], edge: &Edge) -> bool {
 
 pub struct Day25();
 
-impl Day25 {
-    pub fn parse(input: &[String]) -> Graph {
+type Solution = usize;
+impl DaySolver<Solution> for Day25 {
+    type Parsed = Graph;
+    // There is no puzzle for part 2 on the last day
+    const IMPLEMENTED: [bool; 2] = [true, false];
+
+    fn parse(input: &str) -> Result<Graph> {
+        let input: Vec<&str> = input.lines().collect();
         let mut nodes: HashMap<u64, Vec<u64>> = input
             .iter()
-            .map(|line| {
-                let (id, adjs) = line.split_once(": ").unwrap();
+            .enumerate()
+            .map(|(y, line)| {
+                let (id, adjs) = line
+                    .split_once(": ")
+                    .ok_or_else(|| AocError::parse(0, "expected '<id>: <ids>'").on_line(y))?;
                 let id = Graph::hash(id);
                 let adjs = adjs.split(' ').map(Graph::hash).collect();
-                (id, adjs)
+                Ok((id, adjs))
             })
-            .collect();
+            .collect::<Result<_>>()?;
+        if nodes.is_empty() {
+            return Err(AocError::parse(0, "the input is empty"));
+        }
         let keys: Vec<u64> = nodes.keys().copied().collect();
         for key in keys.iter() {
             let adjs = nodes.get(key).unwrap().clone();
@@ -79,14 +91,10 @@ impl Day25 {
                 nodes.get_mut(&adj).unwrap().push(*key);
             }
         }
-        Graph { nodes, keys }
+        Ok(Graph { nodes, keys })
     }
-}
-
-type Solution = usize;
-impl DaySolver<Solution> for Day25 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let graph = Self::parse(&input);
+    fn part1(graph: &Graph) -> Result<Solution> {
+        let disconnected = |_| AocError::solver("the graph is already disconnected");
         // Chose a basically random start node
         let start = graph.keys[0];
         // Iterate through every possible end node, 50/50 chance it's in the other subgraph
@@ -94,13 +102,19 @@ impl DaySolver<Solution> for Day25 {
             let end = graph.keys[i];
             let mut paths = vec![];
             // Find the shortest path to the end
-            let path1 = graph.find_path(start, &[end], &paths).unwrap();
+            let path1 = graph
+                .find_path(start, &[end], &paths)
+                .map_err(disconnected)?;
             paths.append(&mut path1.clone());
             // Find the shortest path to the end, but avoiding all the edges in the last path
-            let path2 = graph.find_path(start, &[end], &paths).unwrap();
+            let path2 = graph
+                .find_path(start, &[end], &paths)
+                .map_err(disconnected)?;
             paths.append(&mut path2.clone());
             // Find the shortest path to the end, but avoiding all the edges in the last two paths
-            let path3 = graph.find_path(start, &[end], &paths).unwrap();
+            let path3 = graph
+                .find_path(start, &[end], &paths)
+                .map_err(disconnected)?;
             paths.append(&mut path3.clone());
             // If there is no longer a path to the end, each of our three paths contains one of the
             // crucial bridging edges we must remove.
@@ -111,7 +125,7 @@ impl DaySolver<Solution> for Day25 {
                             if let Err(size) =
                                 graph.find_path(start, &[end, e1.0, e2.0, e3.0], &[*e1, *e2, *e3])
                             {
-                                return Some((graph.nodes.len() - size) * size);
+                                return Ok((graph.nodes.len() - size) * size);
                             }
                         }
                     }
@@ -120,9 +134,11 @@ impl DaySolver<Solution> for Day25 {
                 // println!("Failed :(");
             }
         }
-        unreachable!();
+        Err(AocError::solver(
+            "there are no three wires that split the graph in two",
+        ))
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        None
+    fn part2(_graph: &Graph) -> Result<Solution> {
+        Err(AocError::Unsolved)
     }
 }
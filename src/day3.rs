@@ -1,82 +1,177 @@
 #![allow(unused)]
-use std::collections::HashMap;
-
-use regex::Regex;
-
-use crate::util::DaySolver;
+use crate::util::grid::{Grid, Pos};
+use crate::util::{AocError, DaySolver, Result};
 
 type Solution = u32;
 
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
-struct Part {
-    symbol: char,
-    x: usize,
-    y: usize,
+/// A number written in the schematic
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Number {
+    pub value: u32,
+    /// Where the first digit is
+    pub pos: Pos,
+    /// How many digits there are
+    pub len: usize,
+}
+
+/// Any punctuation other than `.`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Symbol {
+    pub symbol: char,
+    pub pos: Pos,
 }
 
+/// The engine schematic, with which numbers are next to which symbols
 #[derive(Debug)]
-struct PartNumber {
-    number: u32,
-    part: Part,
+pub struct Schematic {
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    /// The indices of the symbols next to each number
+    number_symbols: Vec<Vec<usize>>,
+    /// The indices of the numbers next to each symbol
+    symbol_numbers: Vec<Vec<usize>>,
 }
 
-pub struct Day3();
+impl TryFrom<&str> for Schematic {
+    type Error = AocError;
 
-impl Day3 {
-    fn find_part(input: &[String], row: usize, col: usize, len: usize) -> Option<Part> {
-        let width = input[0].len() as i32;
-        let height = input.len() as i32;
-        for y in 0.max(row as i32 - 1)..height.min(row as i32 + 2) {
-            let line = &input[y as usize];
-            for x in 0.max(col as i32 - 1)..width.min(col as i32 + len as i32 + 1) {
-                let c = line.chars().nth(x as usize).unwrap();
-                if !['0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '.'].contains(&c) {
-                    return Some(Part {
-                        symbol: c,
-                        x: x as usize,
-                        y: y as usize,
+    fn try_from(value: &str) -> Result<Self> {
+        let grid = Grid::parse(value, |c| {
+            (c.is_ascii_digit() || c.is_ascii_punctuation()).then_some(c)
+        })?;
+        let mut numbers = vec![];
+        let mut symbols = vec![];
+        let mut symbol_at = Grid::new(grid.width(), grid.height(), None);
+        for (y, row) in grid.rows().enumerate() {
+            let mut x = 0;
+            while x < row.len() {
+                if row[x].is_ascii_digit() {
+                    let len = row[x..].iter().take_while(|c| c.is_ascii_digit()).count();
+                    let digits = std::str::from_utf8(&row[x..x + len]).expect("digits are ASCII");
+                    let value = digits
+                        .parse()
+                        .map_err(|e| AocError::parse(x, format!("{e} ({digits:?})")).on_line(y))?;
+                    numbers.push(Number {
+                        value,
+                        pos: (x, y),
+                        len,
                     });
+                    x += len;
+                } else {
+                    if row[x] != b'.' {
+                        symbol_at[(x, y)] = Some(symbols.len());
+                        symbols.push(Symbol {
+                            symbol: char::from(row[x]),
+                            pos: (x, y),
+                        });
+                    }
+                    x += 1;
                 }
             }
         }
-        None
-    }
-    fn parse(input: Vec<String>) -> Vec<PartNumber> {
-        let mut parts = vec![];
-        let digits_pattern = Regex::new("[0-9]+").unwrap();
-        for (row, line) in input.iter().enumerate() {
-            for capture in digits_pattern.captures_iter(line) {
-                let capture = capture.get(0).unwrap();
-                let number = capture.as_str().parse().unwrap();
-                if let Some(part) = Self::find_part(&input, row, capture.start(), capture.len()) {
-                    parts.push(PartNumber { number, part });
+        let mut number_symbols = vec![vec![]; numbers.len()];
+        let mut symbol_numbers = vec![vec![]; symbols.len()];
+        for (n, number) in numbers.iter().enumerate() {
+            let (x, y) = number.pos;
+            for yy in y.saturating_sub(1)..=y + 1 {
+                for xx in x.saturating_sub(1)..=x + number.len {
+                    if let Some(&Some(s)) = symbol_at.get((xx, yy)) {
+                        number_symbols[n].push(s);
+                        symbol_numbers[s].push(n);
+                    }
                 }
             }
         }
-        parts
+        Ok(Schematic {
+            numbers,
+            symbols,
+            number_symbols,
+            symbol_numbers,
+        })
+    }
+}
+
+impl Schematic {
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+    /// The symbols next to the `n`th number
+    pub fn symbols_around(&self, n: usize) -> impl Iterator<Item = &Symbol> {
+        self.number_symbols[n].iter().map(|&s| &self.symbols[s])
+    }
+    /// The numbers next to the `s`th symbol
+    pub fn numbers_around(&self, s: usize) -> impl Iterator<Item = &Number> {
+        self.symbol_numbers[s].iter().map(|&n| &self.numbers[n])
+    }
+    /// The numbers that are next to at least one symbol
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .zip(&self.number_symbols)
+            .filter(|(_, symbols)| !symbols.is_empty())
+            .map(|(number, _)| number)
+    }
+    /// The symbols that are next to exactly `count` numbers, and those numbers
+    pub fn symbols_with_neighbors(
+        &self,
+        count: usize,
+    ) -> impl Iterator<Item = (&Symbol, Vec<&Number>)> {
+        self.symbols
+            .iter()
+            .zip(&self.symbol_numbers)
+            .filter(move |(_, numbers)| numbers.len() == count)
+            .map(|(symbol, numbers)| (symbol, numbers.iter().map(|&n| &self.numbers[n]).collect()))
+    }
+    /// The gear ratio of every `*` that is next to exactly two numbers
+    pub fn gear_ratios(&self) -> impl Iterator<Item = u32> + '_ {
+        self.symbols_with_neighbors(2)
+            .filter(|(symbol, _)| symbol.symbol == '*')
+            .map(|(_, numbers)| numbers[0].value * numbers[1].value)
     }
 }
 
+pub struct Day3();
+
 impl DaySolver<Solution> for Day3 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let data = Self::parse(input);
-        Some(data.iter().map(|p| p.number).sum())
+    type Parsed = Schematic;
+
+    fn parse(input: &str) -> Result<Schematic> {
+        Schematic::try_from(input)
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let data = Self::parse(input);
-        let mut gears = HashMap::new();
-        data.iter().filter(|p| p.part.symbol == '*').for_each(|p| {
-            if !gears.contains_key(&p.part) {
-                gears.insert(p.part.clone(), vec![]);
-            }
-            gears.get_mut(&p.part).unwrap().push(p.number);
-        });
-        Some(
-            gears
-                .iter()
-                .filter(|(_, v)| v.len() == 2)
-                .map(|(_, v)| v[0] * v[1])
-                .sum(),
-        )
+    fn part1(schematic: &Schematic) -> Result<Solution> {
+        Ok(schematic.part_numbers().map(|number| number.value).sum())
+    }
+    fn part2(schematic: &Schematic) -> Result<Solution> {
+        Ok(schematic.gear_ratios().sum())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_adjacency() {
+        let schematic = Schematic::try_from("12*..\n#..3.\n..$..").unwrap();
+        let values = |numbers: Vec<&Number>| numbers.iter().map(|n| n.value).collect::<Vec<_>>();
+        // 12 touches both * and #, and 3 touches both * and $
+        assert_eq!(
+            schematic
+                .symbols_around(0)
+                .map(|s| s.symbol)
+                .collect::<String>(),
+            "*#"
+        );
+        assert_eq!(values(schematic.numbers_around(0).collect()), vec![12, 3]);
+        assert_eq!(schematic.gear_ratios().collect::<Vec<_>>(), vec![36]);
+        let lonely: Vec<char> = schematic
+            .symbols_with_neighbors(1)
+            .map(|(symbol, _)| symbol.symbol)
+            .collect();
+        assert_eq!(lonely, vec!['#', '$']);
+        assert!(Schematic::try_from("1a.").is_err());
     }
 }
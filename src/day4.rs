@@ -1,79 +1,165 @@
 #![allow(unused)]
 use std::collections::HashSet;
 
-use regex::Regex;
-
-use crate::util::DaySolver;
+use crate::util::{column_of, parse_field, parse_lines, AocError, DaySolver, Result};
 
 type Solution = usize;
 
 pub struct Day4();
 
 pub struct Card {
-    winners: [u32; 10],
-    have: [u32; 25],
+    winners: Vec<u32>,
+    have: Vec<u32>,
+}
+
+/// The general parser, for lines of any shape
+impl TryFrom<&str> for Card {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        let (_, numbers) = value
+            .split_once(':')
+            .ok_or_else(|| AocError::parse(0, "expected 'Card <id>:'"))?;
+        let (winners, have) = numbers.split_once('|').ok_or_else(|| {
+            AocError::parse(
+                column_of(value, numbers),
+                "expected a | between the winning numbers and the numbers you have",
+            )
+        })?;
+        let parse_all = |numbers: &str| {
+            numbers
+                .split_whitespace()
+                .map(|number| parse_field(value, number))
+                .collect::<Result<_>>()
+        };
+        Ok(Card {
+            winners: parse_all(winners)?,
+            have: parse_all(have)?,
+        })
+    }
 }
 
-macro_rules! parse_digits {
-    ($value:expr, $a:expr) => {{
-        let x = $value[$a];
-        let y = $value[$a + 1];
-        (if x == b' ' {
-            y - b'0'
-        } else {
-            ((x - b'0') * 10) + y - b'0'
-        }) as u32
-        // $value[$a..$a + 2].trim_start().parse().unwrap()
-    }};
+/// Where the numbers are on every line of the input, worked out from the first one.
+/// Reading them straight from there is much faster than splitting each line.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Layout {
+    len: usize,
+    colon: usize,
+    bar: usize,
+    /// The widest number on the first line, which is the width of every column
+    width: usize,
+    /// Where each column of winning numbers ends
+    winners: Vec<usize>,
+    /// Where each column of numbers we have ends
+    have: Vec<usize>,
 }
 
-impl From<&String> for Card {
-    fn from(value: &String) -> Self {
-        let value = value.as_bytes();
-        let winners = [
-            parse_digits!(value, 10),
-            parse_digits!(value, 13),
-            parse_digits!(value, 16),
-            parse_digits!(value, 19),
-            parse_digits!(value, 22),
-            parse_digits!(value, 25),
-            parse_digits!(value, 28),
-            parse_digits!(value, 31),
-            parse_digits!(value, 34),
-            parse_digits!(value, 37),
-        ];
-        let have = [
-            parse_digits!(value, 42),
-            parse_digits!(value, 45),
-            parse_digits!(value, 48),
-            parse_digits!(value, 51),
-            parse_digits!(value, 54),
-            parse_digits!(value, 57),
-            parse_digits!(value, 60),
-            parse_digits!(value, 63),
-            parse_digits!(value, 66),
-            parse_digits!(value, 69),
-            parse_digits!(value, 72),
-            parse_digits!(value, 75),
-            parse_digits!(value, 78),
-            parse_digits!(value, 81),
-            parse_digits!(value, 84),
-            parse_digits!(value, 87),
-            parse_digits!(value, 90),
-            parse_digits!(value, 93),
-            parse_digits!(value, 96),
-            parse_digits!(value, 99),
-            parse_digits!(value, 102),
-            parse_digits!(value, 105),
-            parse_digits!(value, 108),
-            parse_digits!(value, 111),
-            parse_digits!(value, 114),
-        ];
-        Card { winners, have }
+impl Layout {
+    /// Work out the columns of a line like "Card  1: 41 48 | 83 86  6 31"
+    pub fn infer(line: &str) -> Option<Layout> {
+        let colon = line.find(':')?;
+        let bar = colon + line[colon..].find('|')?;
+        let bytes = line.as_bytes();
+        // The ends of the numbers between `from` and `to`, and the widest one
+        let columns = |from: usize, to: usize| -> Option<(Vec<usize>, usize)> {
+            let mut ends = vec![];
+            let mut width = 0;
+            let mut start = None;
+            for (i, &c) in bytes[from..to].iter().enumerate() {
+                match (c, start) {
+                    (b'0'..=b'9', None) => start = Some(from + i),
+                    (b'0'..=b'9', Some(_)) | (b' ', None) => {}
+                    (b' ', Some(s)) => {
+                        ends.push(from + i);
+                        width = width.max(from + i - s);
+                        start = None;
+                    }
+                    _ => return None,
+                }
+            }
+            if let Some(s) = start {
+                ends.push(to);
+                width = width.max(to - s);
+            }
+            Some((ends, width))
+        };
+        let (winners, winners_width) = columns(colon + 1, bar)?;
+        let (have, have_width) = columns(bar + 1, line.len())?;
+        // Wider numbers might not fit in a u32, and a line without numbers has no columns to read
+        let width = winners_width.max(have_width);
+        if !(1..=9).contains(&width) {
+            return None;
+        }
+        // Every column has to be one space and then `width` bytes, with nothing between them, so
+        // that reading the columns checks every byte of the line
+        let tiles = |ends: &[usize], start: usize, stop: usize| {
+            ends.iter()
+                .enumerate()
+                .all(|(i, &end)| end == start + (i + 1) * (width + 1))
+                && start + ends.len() * (width + 1) == stop
+        };
+        if !tiles(&winners, colon + 1, bar - 1) || !tiles(&have, bar + 1, line.len()) {
+            return None;
+        }
+        Some(Layout {
+            len: line.len(),
+            colon,
+            bar,
+            width,
+            winners,
+            have,
+        })
+    }
+    /// Read the number in the column that ends at `end`, padded on the left with spaces
+    fn read(&self, bytes: &[u8], end: usize) -> Option<u32> {
+        // There has to be a space before the column, or the number is wider than the column
+        if bytes[end - self.width - 1] != b' ' {
+            return None;
+        }
+        let column = &bytes[end - self.width..end];
+        let padding = column.iter().take_while(|c| **c == b' ').count();
+        let digits = &column[padding..];
+        if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        Some(
+            digits
+                .iter()
+                .fold(0, |number, digit| number * 10 + (digit - b'0') as u32),
+        )
+    }
+    /// Read a card, if `line` is laid out like the first line was
+    pub fn parse(&self, line: &str) -> Option<Card> {
+        let bytes = line.as_bytes();
+        if bytes.len() != self.len || bytes[self.colon] != b':' || bytes[self.bar] != b'|' {
+            return None;
+        }
+        let read_all = |ends: &[usize]| {
+            ends.iter()
+                .map(|&end| self.read(bytes, end))
+                .collect::<Option<_>>()
+        };
+        Some(Card {
+            winners: read_all(&self.winners)?,
+            have: read_all(&self.have)?,
+        })
     }
 }
 
 impl Card {
+    /// Check that no number is listed twice on either side of the card
+    fn check_unique(self) -> Result<Self> {
+        for (numbers, side) in [(&self.winners, "winning"), (&self.have, "had")] {
+            let mut seen = HashSet::with_capacity(numbers.len());
+            if let Some(number) = numbers.iter().find(|number| !seen.insert(*number)) {
+                return Err(AocError::parse(
+                    0,
+                    format!("{number} is listed twice among the {side} numbers"),
+                ));
+            }
+        }
+        Ok(self)
+    }
     fn matches(&self) -> usize {
         self.have
             .iter()
@@ -82,12 +168,21 @@ impl Card {
     }
 }
 
-impl Day4 {
-    pub fn parse(input: &[String]) -> Vec<Card> {
-        input.iter().map(|line| line.into()).collect()
+impl DaySolver<Solution> for Day4 {
+    type Parsed = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Vec<Card>> {
+        let layout = input.lines().next().and_then(Layout::infer);
+        parse_lines(input, |line| {
+            match layout.as_ref().and_then(|layout| layout.parse(line)) {
+                Some(card) => card,
+                None => Card::try_from(line)?,
+            }
+            .check_unique()
+        })
     }
-    pub fn _part1(cards: &[Card]) -> Solution {
-        cards
+    fn part1(cards: &Vec<Card>) -> Result<Solution> {
+        Ok(cards
             .iter()
             .map(|card| {
                 let exp = card.matches() as u32;
@@ -97,26 +192,70 @@ impl Day4 {
                     2_usize.pow(exp - 1)
                 }
             })
-            .sum()
+            .sum())
     }
-    pub fn _part2(cards: &[Card]) -> Solution {
+    fn part2(cards: &Vec<Card>) -> Result<Solution> {
         let mut copies = vec![1; cards.len()];
         for i in 0..cards.len() {
-            for j in 1..=cards[i].matches() {
+            let won = cards[i].matches();
+            if i + won >= cards.len() {
+                return Err(AocError::solver(format!(
+                    "card {} wins copies of cards past the end of the table",
+                    i + 1
+                )));
+            }
+            for j in 1..=won {
                 copies[i + j] += copies[i];
             }
         }
-        copies.iter().sum()
+        Ok(copies.iter().sum())
     }
 }
 
-impl DaySolver<Solution> for Day4 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let cards = Self::parse(&input);
-        Some(Self::_part1(&cards))
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1";
+
+    #[test]
+    fn test_layout() {
+        let layout = Layout::infer(EXAMPLE.lines().next().unwrap()).unwrap();
+        assert_eq!(layout.width, 2);
+        assert_eq!((layout.winners.len(), layout.have.len()), (5, 8));
+        for line in EXAMPLE.lines() {
+            let card = layout.parse(line).unwrap();
+            let general = Card::try_from(line).unwrap();
+            assert_eq!((card.winners, card.have), (general.winners, general.have));
+        }
+        // Lines that don't fit the layout are left to the general parser
+        assert!(layout
+            .parse("Card 4: 41 48 83 86 17 | 83 86 6 31 17 9 48 53 1")
+            .is_none());
+        assert!(layout
+            .parse("Card 5: 41 48 83 86 17 | 83 86  6 31 17  9 4 153")
+            .is_none());
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let cards = Self::parse(&input);
-        Some(Self::_part2(&cards))
+
+    #[test]
+    fn test_irregular_first_line() {
+        // Columns of different widths, or with extra spaces between them, don't make a layout
+        assert_eq!(Layout::infer("Card 1: 1 | 123456789"), None);
+        assert_eq!(Layout::infer("Card 1: 1  2 | 3"), None);
+        assert_eq!(Layout::infer("Card 1: 1 | 1234567890"), None);
+        let cards = Day4::parse("Card 1: 1 | 123456789\nCard 2: 12 3 | 4").unwrap();
+        assert_eq!(cards[0].have, vec![123456789]);
+        assert_eq!(cards[1].winners, vec![12, 3]);
+    }
+
+    #[test]
+    fn test_parse() {
+        let cards = Day4::parse(&format!("{EXAMPLE}\nCard 4: 1 2 | 3 4 5 100")).unwrap();
+        assert_eq!(cards[3].have, vec![3, 4, 5, 100]);
+        let matches: Vec<usize> = cards.iter().map(Card::matches).collect();
+        assert_eq!(matches, vec![4, 2, 2, 0]);
+        assert!(Day4::parse("Card 1: 41 48 41 | 83 86  6").is_err());
     }
 }
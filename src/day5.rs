@@ -1,5 +1,6 @@
 #![allow(unused)]
-use crate::util::DaySolver;
+use crate::util::input::Input;
+use crate::util::{parse_field, AocError, DaySolver, Result};
 
 type Solution = u64;
 
@@ -37,16 +38,24 @@ impl PartialOrd for MapRange {
         Some(self.cmp(other))
     }
 }
-impl From<&String> for MapRange {
-    fn from(value: &String) -> Self {
+impl TryFrom<&str> for MapRange {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self> {
         let mut split = value.split(' ');
-        let dest: u64 = split.next().unwrap().parse().unwrap();
-        let source: u64 = split.next().unwrap().parse().unwrap();
-        let width: u64 = split.next().unwrap().parse().unwrap();
-        MapRange {
+        let mut next = || {
+            split
+                .next()
+                .ok_or_else(|| AocError::parse(value.len(), "expected three numbers"))
+                .and_then(|field| parse_field::<u64>(value, field))
+        };
+        let dest = next()?;
+        let source = next()?;
+        let width = next()?;
+        Ok(MapRange {
             range: Range::new(source, source + width),
             dest,
-        }
+        })
     }
 }
 
@@ -61,10 +70,18 @@ pub struct Mapping {
     ranges: Vec<MapRange>,
 }
 
-impl From<&[String]> for Mapping {
-    fn from(value: &[String]) -> Self {
-        let ranges = value.iter().map(MapRange::from).collect();
-        Mapping { ranges }
+/// A map, starting with its "a-to-b map:" header
+impl TryFrom<Input<'_>> for Mapping {
+    type Error = AocError;
+
+    fn try_from(value: Input) -> Result<Self> {
+        let mut ranges = value
+            .numbered_lines()
+            .skip(1)
+            .map(|(y, line)| MapRange::try_from(line).map_err(|e| e.on_line(y)))
+            .collect::<Result<Vec<_>>>()?;
+        ranges.sort();
+        Ok(Mapping { ranges })
     }
 }
 
@@ -128,33 +145,24 @@ pub struct Almanac {
     maps: Vec<Mapping>,
 }
 
-impl From<&Vec<String>> for Almanac {
-    fn from(value: &Vec<String>) -> Self {
-        let seeds = value[0]
+impl TryFrom<&str> for Almanac {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        let mut paragraphs = Input::new(value).paragraphs();
+        let first = paragraphs
+            .next()
+            .ok_or_else(|| AocError::parse(0, "the input is empty"))?
+            .as_str()
+            .trim_end();
+        let seeds = first
             .strip_prefix("seeds: ")
-            .unwrap()
+            .ok_or_else(|| AocError::parse(0, "expected 'seeds: '"))?
             .split(' ')
-            .map(|s| s.parse().unwrap())
-            .collect();
-        let mut maps = Vec::with_capacity(7);
-        let mut mapping = Mapping { ranges: vec![] };
-        let mut start = 3;
-        let mut end = 3;
-        while end < value.len() {
-            if value[end].is_empty() {
-                mapping.ranges.sort();
-                maps.push(mapping);
-                mapping = Mapping { ranges: vec![] };
-                start = end + 2;
-                end = start;
-            } else {
-                mapping.ranges.push(MapRange::from(&value[end]));
-                end += 1;
-            }
-        }
-        mapping.ranges.sort();
-        maps.push(mapping);
-        Almanac { seeds, maps }
+            .map(|s| parse_field(first, s))
+            .collect::<Result<_>>()?;
+        let maps = paragraphs.map(Mapping::try_from).collect::<Result<_>>()?;
+        Ok(Almanac { seeds, maps })
     }
 }
 
@@ -167,23 +175,26 @@ impl Almanac {
 impl Day5 {}
 
 impl DaySolver<Solution> for Day5 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let almanac: Almanac = (&input).into();
-        Some(
-            almanac
-                .seeds
-                .iter()
-                .map(|s| almanac.apply(*s))
-                .min()
-                .unwrap(),
-        )
-    }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let almanac: Almanac = (&input).into();
+    type Parsed = Almanac;
+
+    fn parse(input: &str) -> Result<Almanac> {
+        Almanac::try_from(input)
+    }
+    fn part1(almanac: &Almanac) -> Result<Solution> {
+        almanac
+            .seeds
+            .iter()
+            .map(|s| almanac.apply(*s))
+            .min()
+            .ok_or_else(|| AocError::solver("there are no seeds"))
+    }
+    fn part2(almanac: &Almanac) -> Result<Solution> {
         let mut ranges = vec![];
         let mut i = almanac.seeds.iter();
         while let Some(start) = i.next() {
-            let width = i.next().unwrap();
+            let width = i
+                .next()
+                .ok_or_else(|| AocError::solver("the seeds are not in start/width pairs"))?;
             ranges.push(Range::new(*start, start + width));
         }
         for mapping in &almanac.maps {
@@ -193,7 +204,11 @@ impl DaySolver<Solution> for Day5 {
             }
             ranges = new_ranges;
         }
-        Some(ranges.iter().map(|r| r.start).min().unwrap())
+        ranges
+            .iter()
+            .map(|r| r.start)
+            .min()
+            .ok_or_else(|| AocError::solver("there are no seeds"))
     }
 }
 
@@ -203,7 +218,7 @@ mod test {
     #[test]
     fn test_apply_range_mapping_contained() {
         let mapping = Mapping {
-            ranges: vec![MapRange::from(&"500 5 1".to_string())],
+            ranges: vec![MapRange::try_from("500 5 1").unwrap()],
         };
         assert_eq!(
             mapping.apply_range(&Range::new(0, 10)),
@@ -213,7 +228,7 @@ mod test {
     #[test]
     fn test_apply_range_mapping_contains() {
         let mapping = Mapping {
-            ranges: vec![MapRange::from(&"1000 0 100".to_string())],
+            ranges: vec![MapRange::try_from("1000 0 100").unwrap()],
         };
         assert_eq!(
             mapping.apply_range(&Range::new(10, 20)),
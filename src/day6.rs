@@ -1,5 +1,5 @@
 #![allow(unused)]
-use crate::util::DaySolver;
+use crate::util::{parse_field, AocError, DaySolver, Result};
 
 type Solution = usize;
 
@@ -14,7 +14,7 @@ impl Race {
     fn ways_to_win(&self) -> usize {
         let width = (((self.time * self.time) - (4 * self.distance)) as f64).sqrt();
         // We are cheating and using epsilon values to find only values that win, not just tie
-        if self.time % 2 == 0 {
+        if self.time.is_multiple_of(2) {
             ((((width - 0.0001) / 2.0).floor() as usize) * 2) + 1
         } else {
             2 * ((width + 0.9999) / 2.0).floor() as usize
@@ -31,17 +31,37 @@ impl Race {
     }
 }
 
+/// The sheet of paper, read both as separate races and as one kerned race
+pub struct Sheet {
+    races: [Race; 4],
+    kerned: Race,
+}
+
 impl Day6 {
-    pub fn parse1(input: &[String]) -> [Race; 4] {
-        let time1 = input[0][10..15].trim_start().parse().unwrap();
-        let dist1 = input[1][10..15].trim_start().parse().unwrap();
-        let time2 = input[0][17..22].trim_start().parse().unwrap();
-        let dist2 = input[1][17..22].trim_start().parse().unwrap();
-        let time3 = input[0][24..29].trim_start().parse().unwrap();
-        let dist3 = input[1][24..29].trim_start().parse().unwrap();
-        let time4 = input[0][31..36].trim_start().parse().unwrap();
-        let dist4 = input[1][31..36].trim_start().parse().unwrap();
-        [
+    fn line<'a>(input: &[&'a str], y: usize) -> Result<&'a str> {
+        input
+            .get(y)
+            .copied()
+            .ok_or_else(|| AocError::parse(0, "expected a line of times and a line of distances"))
+    }
+    /// Parse the right-aligned number in columns `start..end` of line `y`
+    fn field(input: &[&str], y: usize, start: usize, end: usize) -> Result<usize> {
+        let line = Self::line(input, y)?;
+        let field = line
+            .get(start..end)
+            .ok_or_else(|| AocError::parse(line.len(), "line is too short").on_line(y))?;
+        parse_field(line, field.trim_start()).map_err(|e| e.on_line(y))
+    }
+    fn parse1(input: &[&str]) -> Result<[Race; 4]> {
+        let time1 = Self::field(input, 0, 10, 15)?;
+        let dist1 = Self::field(input, 1, 10, 15)?;
+        let time2 = Self::field(input, 0, 17, 22)?;
+        let dist2 = Self::field(input, 1, 17, 22)?;
+        let time3 = Self::field(input, 0, 24, 29)?;
+        let dist3 = Self::field(input, 1, 24, 29)?;
+        let time4 = Self::field(input, 0, 31, 36)?;
+        let dist4 = Self::field(input, 1, 31, 36)?;
+        Ok([
             Race {
                 time: time1,
                 distance: dist1,
@@ -58,39 +78,43 @@ impl Day6 {
                 time: time4,
                 distance: dist4,
             },
-        ]
+        ])
     }
-    pub fn parse2(input: &[String]) -> Race {
-        let time = input[0][10..]
+    /// Parse line `y` as one big number, ignoring the label and the spaces between digits
+    fn kerned(input: &[&str], y: usize) -> Result<usize> {
+        let line = Self::line(input, y)?;
+        let (_, digits) = line
+            .split_once(':')
+            .ok_or_else(|| AocError::parse(0, "expected a label").on_line(y))?;
+        digits
             .chars()
             .filter(|c| c != &' ')
             .collect::<String>()
             .parse()
-            .unwrap();
-        let distance = input[1][10..]
-            .chars()
-            .filter(|c| c != &' ')
-            .collect::<String>()
-            .parse()
-            .unwrap();
-        Race { time, distance }
+            .map_err(|e| AocError::parse(line.len() - digits.len(), e).on_line(y))
+    }
+    fn parse2(input: &[&str]) -> Result<Race> {
+        let time = Self::kerned(input, 0)?;
+        let distance = Self::kerned(input, 1)?;
+        Ok(Race { time, distance })
     }
 }
 
 impl DaySolver<Solution> for Day6 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let races = Self::parse1(&input);
-        Some(
-            races
-                .iter()
-                .map(Race::ways_to_win)
-                .reduce(|a, b| a * b)
-                .unwrap(),
-        )
+    type Parsed = Sheet;
+
+    fn parse(input: &str) -> Result<Sheet> {
+        let input: Vec<&str> = input.lines().collect();
+        Ok(Sheet {
+            races: Self::parse1(&input)?,
+            kerned: Self::parse2(&input)?,
+        })
+    }
+    fn part1(sheet: &Sheet) -> Result<Solution> {
+        Ok(sheet.races.iter().map(Race::ways_to_win).product())
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let race = Self::parse2(&input);
-        Some(race.ways_to_win())
+    fn part2(sheet: &Sheet) -> Result<Solution> {
+        Ok(sheet.kerned.ways_to_win())
     }
 }
 
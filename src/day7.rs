@@ -1,14 +1,16 @@
 #![allow(unused)]
-use crate::util::DaySolver;
+use crate::util::{parse_field, parse_lines, AocError, DaySolver, Result};
 
 type Solution = usize;
 
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct Card(usize);
 
-impl From<char> for Card {
-    fn from(value: char) -> Self {
-        Card(match value {
+impl TryFrom<char> for Card {
+    type Error = AocError;
+
+    fn try_from(value: char) -> Result<Self> {
+        Ok(Card(match value {
             '2' => 2,
             '3' => 3,
             '4' => 4,
@@ -22,14 +24,14 @@ impl From<char> for Card {
             'Q' => 12,
             'K' => 13,
             'A' => 14,
-            _ => panic!(),
-        })
+            _ => return Err(AocError::parse(0, format!("{value} is not a card"))),
+        }))
     }
 }
 
 impl Card {
-    fn from2(value: char) -> Self {
-        Card(match value {
+    fn from2(value: char) -> Result<Self> {
+        Ok(Card(match value {
             // J is remapped to 1 in part 2
             'J' => 1,
             '2' => 2,
@@ -44,8 +46,8 @@ impl Card {
             'Q' => 12,
             'K' => 13,
             'A' => 14,
-            _ => panic!(),
-        })
+            _ => return Err(AocError::parse(0, format!("{value} is not a card"))),
+        }))
     }
 }
 
@@ -117,52 +119,61 @@ impl Hand {
 
 pub struct Day7();
 
+/// Every hand, scored without jokers for part 1 and with them for part 2
+pub struct Hands {
+    plain: Vec<Hand>,
+    jokers: Vec<Hand>,
+}
+
 impl Day7 {
-    pub fn parse1(input: &[String]) -> Vec<Hand> {
-        input
-            .iter()
-            .map(|line| {
-                let mut i = line.chars();
-                let cards = [
-                    Card::from(i.next().unwrap()),
-                    Card::from(i.next().unwrap()),
-                    Card::from(i.next().unwrap()),
-                    Card::from(i.next().unwrap()),
-                    Card::from(i.next().unwrap()),
-                ];
-                let bid = line[6..].parse().unwrap();
-                Hand::new1(cards, bid)
-            })
-            .collect()
+    fn parse_hand(
+        line: &str,
+        card: fn(char) -> Result<Card>,
+        hand: fn([Card; 5], usize) -> Hand,
+    ) -> Result<Hand> {
+        let (cards_str, bid_str) = line
+            .split_once(' ')
+            .ok_or_else(|| AocError::parse(0, "expected '<cards> <bid>'"))?;
+        let cards: Vec<Card> = cards_str
+            .chars()
+            .enumerate()
+            .map(|(x, c)| card(c).map_err(|_| AocError::parse(x, format!("{c} is not a card"))))
+            .collect::<Result<_>>()?;
+        let cards: [Card; 5] = cards
+            .try_into()
+            .map_err(|_| AocError::parse(0, "expected a hand of 5 cards"))?;
+        let bid = parse_field(line, bid_str)?;
+        Ok(hand(cards, bid))
     }
-    pub fn parse2(input: &[String]) -> Vec<Hand> {
-        input
-            .iter()
-            .map(|line| {
-                let mut i = line.chars();
-                let cards = [
-                    Card::from2(i.next().unwrap()),
-                    Card::from2(i.next().unwrap()),
-                    Card::from2(i.next().unwrap()),
-                    Card::from2(i.next().unwrap()),
-                    Card::from2(i.next().unwrap()),
-                ];
-                let bid = line[6..].parse().unwrap();
-                Hand::new2(cards, bid)
-            })
-            .collect()
+    fn parse1(input: &str) -> Result<Vec<Hand>> {
+        parse_lines(input, |line| {
+            Self::parse_hand(line, Card::try_from, Hand::new1)
+        })
+    }
+    fn parse2(input: &str) -> Result<Vec<Hand>> {
+        parse_lines(input, |line| {
+            Self::parse_hand(line, Card::from2, Hand::new2)
+        })
     }
 }
 
 impl DaySolver<Solution> for Day7 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let mut hands = Self::parse1(&input);
+    type Parsed = Hands;
+
+    fn parse(input: &str) -> Result<Hands> {
+        Ok(Hands {
+            plain: Self::parse1(input)?,
+            jokers: Self::parse2(input)?,
+        })
+    }
+    fn part1(hands: &Hands) -> Result<Solution> {
+        let mut hands = hands.plain.clone();
         hands.sort();
-        Some(hands.iter().enumerate().map(|(i, h)| (i + 1) * h.bid).sum())
+        Ok(hands.iter().enumerate().map(|(i, h)| (i + 1) * h.bid).sum())
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let mut hands = Self::parse2(&input);
+    fn part2(hands: &Hands) -> Result<Solution> {
+        let mut hands = hands.jokers.clone();
         hands.sort();
-        Some(hands.iter().enumerate().map(|(i, h)| (i + 1) * h.bid).sum())
+        Ok(hands.iter().enumerate().map(|(i, h)| (i + 1) * h.bid).sum())
     }
 }
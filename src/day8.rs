@@ -3,14 +3,16 @@ use std::collections::HashMap;
 
 use num::Integer;
 
-use crate::util::DaySolver;
+use crate::util::input::Input;
+use crate::util::{AocError, DaySolver, Result};
 
 pub type Directions = Vec<usize>;
 
 #[derive(Debug)]
 pub struct Graph {
-    start: usize,
-    end: usize,
+    // AAA and ZZZ, which only part 1 needs
+    start: Option<usize>,
+    end: Option<usize>,
     // All node indices that end in A
     all_starts: Vec<usize>,
     // The left node, the right node, and whether or not this node ends in Z
@@ -23,23 +25,52 @@ pub struct Docs {
     graph: Graph,
 }
 
-impl From<&[String]> for Docs {
-    fn from(value: &[String]) -> Self {
-        let directions = value[0]
+impl TryFrom<&str> for Docs {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        let mut paragraphs = Input::new(value).paragraphs();
+        let first = paragraphs
+            .next()
+            .ok_or_else(|| AocError::parse(0, "the input is empty"))?
+            .as_str()
+            .trim_end();
+        if first.contains('\n') {
+            return Err(AocError::parse(0, "expected a line of directions"));
+        }
+        let directions = first
             .chars()
-            .map(|c| match c {
-                'L' => 0,
-                'R' => 1,
-                _ => panic!(),
+            .enumerate()
+            .map(|(x, c)| match c {
+                'L' => Ok(0),
+                'R' => Ok(1),
+                _ => Err(AocError::parse(x, format!("{c} is not a direction"))),
             })
-            .collect();
-        let node_name_map: HashMap<&str, usize> = value[2..]
+            .collect::<Result<_>>()?;
+        // Every node line looks like "AAA = (BBB, CCC)"
+        let nodes_input = paragraphs
+            .next()
+            .ok_or_else(|| AocError::parse(0, "expected the nodes").on_line(2))?;
+        let nodes_str: Vec<&str> = nodes_input.lines().collect();
+        let offset = nodes_input.first_line();
+        for (y, line) in nodes_str.iter().enumerate() {
+            if line.len() != 16 || !line.is_char_boundary(3) || !line.is_char_boundary(15) {
+                return Err(AocError::parse(0, "expected 'AAA = (BBB, CCC)'").on_line(y + offset));
+            }
+        }
+        let node_name_map: HashMap<&str, usize> = nodes_str
             .iter()
             .enumerate()
             .map(|(i, line)| (&line[0..3], i))
             .collect();
-        let start = *node_name_map.get(&"AAA").unwrap();
-        let end = *node_name_map.get(&"ZZZ").unwrap();
+        let find = |name: &str| {
+            node_name_map
+                .get(name)
+                .copied()
+                .ok_or_else(|| AocError::parse(0, format!("there is no node {name}")))
+        };
+        let start = node_name_map.get("AAA").copied();
+        let end = node_name_map.get("ZZZ").copied();
         let all_starts = node_name_map
             .iter()
             .filter_map(|(line, i)| {
@@ -51,93 +82,114 @@ impl From<&[String]> for Docs {
             })
             .copied()
             .collect();
-        let nodes = value[2..]
+        let nodes = nodes_str
             .iter()
-            .map(|line| {
-                (
-                    [
-                        *node_name_map.get(&line[7..10]).unwrap(),
-                        *node_name_map.get(&line[12..15]).unwrap(),
-                    ],
-                    line.chars().nth(2) == Some('Z'),
-                )
+            .enumerate()
+            .map(|(y, line)| {
+                let left = find(&line[7..10]).map_err(|e| e.on_line(y + offset))?;
+                let right = find(&line[12..15]).map_err(|e| e.on_line(y + offset))?;
+                Ok(([left, right], line.chars().nth(2) == Some('Z')))
             })
-            .collect();
+            .collect::<Result<_>>()?;
         let graph = Graph {
             start,
             end,
             all_starts,
             nodes,
         };
-        Docs { directions, graph }
+        Ok(Docs { directions, graph })
     }
 }
 
 type Solution = u64;
 pub struct Day8();
 
-impl Day8 {
-    pub fn parse(input: &[String]) -> Docs {
-        Docs::from(input)
-    }
-}
-
 impl DaySolver<Solution> for Day8 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let docs = Self::parse(&input[..]);
-        let mut node = docs.graph.start;
+    type Parsed = Docs;
+
+    fn parse(input: &str) -> Result<Docs> {
+        Docs::try_from(input)
+    }
+    fn part1(docs: &Docs) -> Result<Solution> {
+        let missing = |name| AocError::solver(format!("there is no node {name}"));
+        let mut node = docs.graph.start.ok_or_else(|| missing("AAA"))?;
+        let end = docs.graph.end.ok_or_else(|| missing("ZZZ"))?;
         let mut steps = 0;
         for dir in docs.directions.iter().cycle() {
-            if node == docs.graph.end {
+            if node == end {
                 break;
             }
             node = docs.graph.nodes[node].0[*dir];
             steps += 1;
         }
-        Some(steps)
+        Ok(steps)
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let docs = Self::parse(&input[..]);
+    fn part2(docs: &Docs) -> Result<Solution> {
         let mut nodes = docs.graph.all_starts.clone();
 
-        Some(
-            nodes
-                .iter()
-                // Walk every node and identify when they find the first Z and how long it takes to find the second
-                .map(|node| {
-                    let mut steps: u64 = 0;
-                    let mut node = *node;
-                    let mut first: u64 = 0;
-                    for (steps, dir) in (0_u64..).zip(docs.directions.iter().cycle()) {
-                        let (next, is_z) = docs.graph.nodes[node];
-                        if is_z {
-                            if first == 0 {
-                                first = steps;
-                            } else {
-                                // First element is how many steps it took to find the first Z
-                                // Second element is how many steps from there it took to find the second,
-                                // which is the same thing as the length of the cycle.
-                                return (first, steps - first);
-                            }
+        Ok(nodes
+            .iter()
+            // Walk every node and identify when they find the first Z and how long it takes to find the second
+            .map(|node| {
+                let mut steps: u64 = 0;
+                let mut node = *node;
+                let mut first: u64 = 0;
+                for (steps, dir) in (0_u64..).zip(docs.directions.iter().cycle()) {
+                    let (next, is_z) = docs.graph.nodes[node];
+                    if is_z {
+                        if first == 0 {
+                            first = steps;
+                        } else {
+                            // First element is how many steps it took to find the first Z
+                            // Second element is how many steps from there it took to find the second,
+                            // which is the same thing as the length of the cycle.
+                            return (first, steps - first);
                         }
-                        node = next[*dir];
-                    }
-                    // The loop runs forever, it's not possible to get here.
-                    panic!()
-                })
-                // For the first pair of ghosts, figure out the first moment they are both on a Z.
-                // Use that to create a "virtual" ghost with a much longer cycle.
-                // Repeat the process until there is only one ghost remaining.
-                .reduce(|(start, incr), (a, b)| {
-                    let mut steps = start;
-                    while (steps - a) % b != 0 {
-                        steps += incr;
                     }
-                    // The length of the cycle is the least common multiple of the two subcycles
-                    (steps, incr.lcm(&a))
-                })
-                .unwrap()
-                .0,
+                    node = next[*dir];
+                }
+                // The loop runs forever, it's not possible to get here.
+                panic!()
+            })
+            // For the first pair of ghosts, figure out the first moment they are both on a Z.
+            // Use that to create a "virtual" ghost with a much longer cycle.
+            // Repeat the process until there is only one ghost remaining.
+            .reduce(|(start, incr), (a, b)| {
+                let mut steps = start;
+                while steps < a || (steps - a) % b != 0 {
+                    steps += incr;
+                }
+                // The length of the cycle is the least common multiple of the two subcycles
+                (steps, incr.lcm(&b))
+            })
+            .ok_or_else(|| AocError::solver("there are no nodes ending in A"))?
+            .0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ghosts_without_aaa() {
+        let docs = Day8::parse(
+            "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)",
         )
+        .unwrap();
+        assert_eq!(Day8::part2(&docs).unwrap(), 6);
+        assert_eq!(
+            Day8::part1(&docs).unwrap_err().to_string(),
+            AocError::solver("there is no node AAA").to_string()
+        );
     }
 }
@@ -1,17 +1,10 @@
 #![allow(unused)]
-use crate::util::DaySolver;
+use crate::util::{parse_field, parse_lines, DaySolver, Result};
 use std::iter::once;
 
 pub struct Day9();
 
 impl Day9 {
-    pub fn parse(input: &[String]) -> Vec<Vec<i64>> {
-        input
-            .iter()
-            .map(|line| line.split(' ').map(|n| n.parse().unwrap()).collect())
-            .collect()
-    }
-
     fn extrapolate_forward(mut items: &mut [i64]) -> i64 {
         if items.iter().all(|i| *i == 0) {
             return 0;
@@ -38,20 +31,25 @@ impl Day9 {
 
 type Solution = i64;
 impl DaySolver<Solution> for Day9 {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let mut data = Self::parse(&input);
-        Some(
-            data.iter_mut()
-                .map(|mut line| Self::extrapolate_forward(line))
-                .sum(),
-        )
+    type Parsed = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Vec<Vec<i64>>> {
+        parse_lines(input, |line| {
+            line.split(' ').map(|n| parse_field(line, n)).collect()
+        })
+    }
+    fn part1(data: &Vec<Vec<i64>>) -> Result<Solution> {
+        let mut data = data.clone();
+        Ok(data
+            .iter_mut()
+            .map(|mut line| Self::extrapolate_forward(line))
+            .sum())
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        let mut data = Self::parse(&input);
-        Some(
-            data.iter_mut()
-                .map(|mut line| Self::extrapolate_back(line))
-                .sum(),
-        )
+    fn part2(data: &Vec<Vec<i64>>) -> Result<Solution> {
+        let mut data = data.clone();
+        Ok(data
+            .iter_mut()
+            .map(|mut line| Self::extrapolate_back(line))
+            .sum())
     }
 }
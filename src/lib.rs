@@ -1,28 +1,31 @@
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
-
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-pub mod day19;
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
-pub mod day24;
-pub mod day25;
-
 pub mod util;
+
+days! {
+    2023 {
+        1 => day1::Day1, "Trebuchet?!";
+        2 => day2::Day2, "Cube Conundrum";
+        3 => day3::Day3, "Gear Ratios";
+        4 => day4::Day4, "Scratchcards";
+        5 => day5::Day5, "If You Give A Seed A Fertilizer";
+        6 => day6::Day6, "Wait For It";
+        7 => day7::Day7, "Camel Cards";
+        8 => day8::Day8, "Haunted Wasteland";
+        9 => day9::Day9, "Mirage Maintenance";
+        10 => day10::Day10, "Pipe Maze";
+        11 => day11::Day11, "Cosmic Expansion";
+        12 => day12::Day12, "Hot Springs";
+        13 => day13::Day13, "Point of Incidence";
+        14 => day14::Day14, "Parabolic Reflector Dish";
+        15 => day15::Day15, "Lens Library";
+        16 => day16::Day16, "The Floor Will Be Lava";
+        17 => day17::Day17, "Clumsy Crucible";
+        18 => day18::Day18, "Lavaduct Lagoon";
+        19 => day19::Day19, "Aplenty";
+        20 => day20::Day20, "Pulse Propagation";
+        21 => day21::Day21, "Step Counter";
+        22 => day22::Day22, "Sand Slabs";
+        23 => day23::Day23, "A Long Walk";
+        24 => day24::Day24, "Never Tell Me The Odds";
+        25 => day25::Day25, "Snowverload";
+    }
+}
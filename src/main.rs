@@ -1,40 +1,42 @@
-use clap::Parser;
-
-mod util;
-
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-
-use crate::util::DaySolver;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use clap::{Parser, Subcommand};
+
+use advent_of_code_2023::day1::{Day1, Strictness};
+use advent_of_code_2023::util::ledger::Ledger;
+use advent_of_code_2023::util::output::{self, Format, Record, Status};
+use advent_of_code_2023::util::pool::Solution;
+use advent_of_code_2023::util::scaffold;
+use advent_of_code_2023::util::session;
+use advent_of_code_2023::util::submit::Outcome;
+use advent_of_code_2023::util::timing::{self, TimingReport};
+use advent_of_code_2023::util::{self, AocError, InputSource, ParsedInput, Result, Solver};
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Create the solution file of a new day from template.rs and register it in src/lib.rs
+    New {
+        /// The day to create
+        day: usize,
+
+        /// The title of the puzzle, if it is not pulled from the puzzle page
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Also download the title, the examples and the input of the day
+        #[arg(long)]
+        pull: bool,
+    },
+}
 
 #[derive(Parser, Debug)]
 #[command(author, version, about)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Run all days
     #[arg(long)]
     all: bool,
@@ -43,107 +45,487 @@ struct Args {
     #[arg()]
     day: Option<usize>,
 
-    /// Clear the cookie
+    /// The event to run days from
+    #[arg(long, global = true, default_value_t = util::DEFAULT_YEAR)]
+    year: usize,
+
+    /// Clear the saved session cookie, so that a new one is asked for
     #[arg(long)]
     cookie: bool,
 
+    /// Load a page that needs the session cookie, to see if the website still accepts it
+    #[arg(long)]
+    cookie_check: bool,
+
+    /// Use the session cookie saved under this name, for sharing a machine between accounts
+    #[arg(long, default_value = session::DEFAULT_PROFILE)]
+    profile: String,
+
+    /// Read the input from this file instead of the cached puzzle input
+    #[arg(long, value_name = "PATH", requires = "day", conflicts_with_all = ["stdin", "example"])]
+    input: Option<PathBuf>,
+
+    /// Read the input from stdin instead of the cached puzzle input
+    #[arg(long, requires = "day", conflicts_with = "example")]
+    stdin: bool,
+
+    /// Run on the Kth example from the puzzle page, as saved by --pull-examples
+    #[arg(long, value_name = "K")]
+    example: Option<usize>,
+
     /// Download input again
     #[arg(long)]
     pull: bool,
+
+    /// Rerun the day in a child process whenever its input or examples change
+    #[arg(long, requires = "day", conflicts_with_all = ["stdin", "all", "record", "submit"])]
+    watch: bool,
+
+    /// Wait for the puzzle to unlock, then download the input and run the day right away
+    #[arg(long, requires = "day", conflicts_with_all = ["input", "stdin", "example"])]
+    wait: bool,
+
+    /// Download the examples from the puzzle page of the day, with their expected answers
+    #[arg(long, requires = "day")]
+    pull_examples: bool,
+
+    /// List the available days and which parts they implement
+    #[arg(long)]
+    list: bool,
+
+    /// Leave lines without digits out of the day 1 total instead of failing, like the lines of
+    /// the part 2 example that part 1 can't read
+    #[arg(long, conflicts_with_all = ["submit", "record"])]
+    lenient: bool,
+
+    /// Submit the answer to this part of the day
+    #[arg(
+        long,
+        value_name = "PART",
+        requires = "day",
+        conflicts_with_all = ["input", "stdin", "example"],
+        value_parser = clap::value_parser!(u8).range(1..=2)
+    )]
+    submit: Option<u8>,
+
+    /// How to print the answers
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// How many days (or parts of days) to solve at once with --all
+    #[arg(long, short, default_value_t = 1)]
+    jobs: usize,
+
+    /// Time loading the input and solving each part, instead of printing the answers
+    #[arg(long)]
+    time: bool,
+
+    /// How many times to run each stage when timing, reporting the mean
+    #[arg(long, default_value_t = 1, requires = "time")]
+    repeat: usize,
+
+    /// Save the timings as JSON in this file
+    #[arg(long, value_name = "PATH", requires = "time")]
+    save_timings: Option<PathBuf>,
+
+    /// Record the answers that are found in the answers/ ledger
+    #[arg(long, conflicts_with_all = ["input", "stdin", "example"])]
+    record: bool,
+
+    /// Rerun every day with recorded answers and fail if any answer changed
+    #[arg(long)]
+    check: bool,
+}
+
+/// Print the outcome of one part, returning false if it failed
+fn report(indent: &str, part: usize, result: &Result<String>) -> bool {
+    match result {
+        Ok(solution) => {
+            println!("{indent}Part {part}: {solution}");
+            true
+        }
+        Err(AocError::Unsolved) => true,
+        Err(e) => {
+            eprintln!("{indent}Part {part} failed: {e}");
+            false
+        }
+    }
+}
+
+/// Print a line about what is going on. It goes to stdout only when that is not reserved for
+/// structured results.
+fn status(format: Format, line: impl Display) {
+    if format == Format::Text {
+        println!("{line}");
+    } else {
+        eprintln!("{line}");
+    }
+}
+
+fn list(year: usize, format: Format) {
+    for solver in util::solvers_for(year) {
+        let parts = match solver.implemented {
+            [true, true] => "parts 1 and 2",
+            [true, false] => "part 1",
+            [false, true] => "part 2",
+            [false, false] => "no parts",
+        };
+        status(
+            format,
+            format!("Day {:>2}: {} ({parts})", solver.day, solver.title),
+        );
+    }
+}
+
+/// Submit the solution of one part, returning false unless it was correct
+fn submit(format: Format, year: usize, day: usize, part: usize, result: &Result<String>) -> bool {
+    let Ok(answer) = result else {
+        eprintln!("Part {part} has no answer to submit");
+        return false;
+    };
+    match util::submit::submit(year, day, part, answer) {
+        Ok(outcome) => {
+            status(format, &outcome);
+            outcome == Outcome::Correct
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            false
+        }
+    }
+}
+
+/// Record the solutions of a day in the ledger, if there is one
+fn record(ledger: &mut Option<Ledger>, day: usize, solutions: &[Solution; 2]) {
+    let Some(ledger) = ledger else {
+        return;
+    };
+    for (i, solution) in solutions.iter().enumerate() {
+        if let Ok(answer) = &solution.result {
+            ledger.record(day, i + 1, answer);
+        }
+    }
+}
+
+/// Print the solutions of a day, or keep them for structured output.
+/// Returns false if either part failed.
+fn output(
+    args: &Args,
+    records: &mut Vec<Record>,
+    indent: &str,
+    day: usize,
+    solutions: &[Solution; 2],
+) -> bool {
+    if args.format == Format::Text {
+        let success = report(indent, 1, &solutions[0].result);
+        report(indent, 2, &solutions[1].result) && success
+    } else {
+        for (i, solution) in solutions.iter().enumerate() {
+            records.push(Record::new(args.year, day, i + 1, solution));
+        }
+        records[records.len() - 2..]
+            .iter()
+            .all(|record| record.status != Status::Error)
+    }
+}
+
+/// Rerun every recorded part of the year, returning false if any answer changed
+fn check(format: Format, year: usize, ledger: &Ledger) -> bool {
+    let mut unchanged = true;
+    for solver in util::solvers_for(year) {
+        let day = solver.day;
+        let mut parsed = None;
+        for part in 1..=2 {
+            let Some(recorded) = ledger.get(day, part) else {
+                if solver.implemented[part - 1] {
+                    status(
+                        format,
+                        format!("Day {day:>2} part {part}: no recorded answer"),
+                    );
+                }
+                continue;
+            };
+            let input = parsed.get_or_insert_with(|| solver.load(&InputSource::Cached));
+            let result = match input {
+                Ok(input) => solver.solve(part, input),
+                Err(e) => Err(e.clone()),
+            };
+            match result {
+                Ok(answer) if answer == recorded => {
+                    status(format, format!("Day {day:>2} part {part}: ok"))
+                }
+                Ok(answer) => {
+                    eprintln!("Day {day:>2} part {part}: changed from {recorded} to {answer}");
+                    unchanged = false;
+                }
+                Err(e) => {
+                    eprintln!("Day {day:>2} part {part}: expected {recorded}, but {e}");
+                    unchanged = false;
+                }
+            }
+        }
+    }
+    unchanged
+}
+
+/// Solve both parts of each day on the worker pool, parsing the input of a day only once
+fn solve_days(
+    solvers: &[&Solver],
+    jobs: usize,
+    source: &InputSource,
+    report: impl FnMut(usize, [Solution; 2]),
+) {
+    let parsed: Vec<OnceLock<Result<ParsedInput>>> =
+        solvers.iter().map(|_| OnceLock::new()).collect();
+    util::pool::solve_all(
+        solvers.len(),
+        jobs,
+        |i, part| {
+            let solver = solvers[i];
+            if solver.part(part).is_none() {
+                return Solution::failed(AocError::Unsolved);
+            }
+            match parsed[i].get_or_init(|| solver.load(source)) {
+                Ok(input) => Solution::timed(|| solver.solve(part, input)),
+                Err(e) => Solution::failed(e.clone()),
+            }
+        },
+        report,
+    );
 }
 
-fn solve_part1(day: usize) -> Option<String> {
-    match day {
-        1 => crate::day1::Day1::solve_part1(day),
-        2 => crate::day2::Day2::solve_part1(day),
-        3 => crate::day3::Day3::solve_part1(day),
-        4 => crate::day4::Day4::solve_part1(day),
-        5 => crate::day5::Day5::solve_part1(day),
-        6 => crate::day6::Day6::solve_part1(day),
-        7 => crate::day7::Day7::solve_part1(day),
-        8 => crate::day8::Day8::solve_part1(day),
-        9 => crate::day9::Day9::solve_part1(day),
-        10 => crate::day10::Day10::solve_part1(day),
-        11 => crate::day11::Day11::solve_part1(day),
-        12 => crate::day12::Day12::solve_part1(day),
-        13 => crate::day13::Day13::solve_part1(day),
-        14 => crate::day14::Day14::solve_part1(day),
-        15 => crate::day15::Day15::solve_part1(day),
-        16 => crate::day16::Day16::solve_part1(day),
-        17 => crate::day17::Day17::solve_part1(day),
-        18 => crate::day18::Day18::solve_part1(day),
-        19 => crate::day19::Day19::solve_part1(day),
-        20 => crate::day20::Day20::solve_part1(day),
-        21 => crate::day21::Day21::solve_part1(day),
-        22 => crate::day22::Day22::solve_part1(day),
-        23 => crate::day23::Day23::solve_part1(day),
-        24 => crate::day24::Day24::solve_part1(day),
-        25 => crate::day25::Day25::solve_part1(day),
-        _ => panic!("Invalid day {}", day),
+/// Time every day that was asked for and print a table of the timings, returning false if any day failed
+fn time(args: &Args) -> bool {
+    let mut success = true;
+    let source = source(args);
+    let solvers: Vec<_> = match args.day {
+        Some(day) if !args.all => util::solver(args.year, day).into_iter().collect(),
+        _ => util::solvers_for(args.year).collect(),
+    };
+    if solvers.is_empty() {
+        eprintln!("There is nothing to time");
+        return false;
     }
+    let mut report = TimingReport {
+        year: args.year,
+        repeat: args.repeat,
+        days: vec![],
+    };
+    for solver in solvers {
+        match timing::time_day(solver, &source, args.repeat) {
+            Ok(timing) => report.days.push(timing),
+            Err(AocError::Unsolved) => {}
+            Err(e) => {
+                eprintln!("Day {} failed: {e}", solver.day);
+                success = false;
+            }
+        }
+    }
+    status(args.format, report.table().trim_end());
+    if let Some(path) = &args.save_timings {
+        if let Err(e) = report.save(path) {
+            eprintln!("{e}");
+            success = false;
+        }
+    }
+    success
 }
 
-fn solve_part2(day: usize) -> Option<String> {
-    match day {
-        1 => crate::day1::Day1::solve_part2(day),
-        2 => crate::day2::Day2::solve_part2(day),
-        3 => crate::day3::Day3::solve_part2(day),
-        4 => crate::day4::Day4::solve_part2(day),
-        5 => crate::day5::Day5::solve_part2(day),
-        6 => crate::day6::Day6::solve_part2(day),
-        7 => crate::day7::Day7::solve_part2(day),
-        8 => crate::day8::Day8::solve_part2(day),
-        9 => crate::day9::Day9::solve_part2(day),
-        10 => crate::day10::Day10::solve_part2(day),
-        11 => crate::day11::Day11::solve_part2(day),
-        12 => crate::day12::Day12::solve_part2(day),
-        13 => crate::day13::Day13::solve_part2(day),
-        14 => crate::day14::Day14::solve_part2(day),
-        15 => crate::day15::Day15::solve_part2(day),
-        16 => crate::day16::Day16::solve_part2(day),
-        17 => crate::day17::Day17::solve_part2(day),
-        18 => crate::day18::Day18::solve_part2(day),
-        19 => crate::day19::Day19::solve_part2(day),
-        20 => crate::day20::Day20::solve_part2(day),
-        21 => crate::day21::Day21::solve_part2(day),
-        22 => crate::day22::Day22::solve_part2(day),
-        23 => crate::day23::Day23::solve_part2(day),
-        24 => crate::day24::Day24::solve_part2(day),
-        25 => crate::day25::Day25::solve_part2(day),
-        _ => panic!("Invalid day {}", day),
+/// Where the answers should be solved from
+fn source(args: &Args) -> InputSource {
+    if let Some(path) = &args.input {
+        InputSource::File(path.clone())
+    } else if args.stdin {
+        InputSource::Stdin
+    } else if let Some(k) = args.example {
+        InputSource::Example(k)
+    } else {
+        InputSource::Cached
+    }
+}
+
+/// Rerun a day every time its input changes, comparing the answers with the previous run
+fn watch(args: &Args, day: usize) -> ! {
+    let source = source(args);
+    let mut child_args = vec![
+        "--year".to_string(),
+        args.year.to_string(),
+        day.to_string(),
+        "--profile".to_string(),
+        args.profile.clone(),
+    ];
+    match &source {
+        InputSource::File(path) => {
+            child_args.extend(["--input".to_string(), path.display().to_string()])
+        }
+        InputSource::Example(k) => child_args.extend(["--example".to_string(), k.to_string()]),
+        InputSource::Cached | InputSource::Stdin => {}
+    }
+    let files = util::watch::watched_files(args.year, day, &source);
+    eprintln!("Watching {} files, press Ctrl+C to stop", files.len());
+    let mut watcher = util::watch::Watcher::new(files);
+    let mut previous: Vec<output::Record> = vec![];
+    loop {
+        match util::watch::run_child(&child_args) {
+            Ok(records) => {
+                for record in &records {
+                    let before = previous.iter().find(|before| before.part == record.part);
+                    println!("{}", util::watch::compare(record, before));
+                }
+                previous = records;
+            }
+            Err(e) => eprintln!("{e}"),
+        }
+        for path in watcher.wait() {
+            println!("\n{} changed", path.display());
+        }
     }
 }
 
 fn main() {
     let args = Args::parse();
+    let mut success = true;
+    if let Err(e) = session::select_profile(&args.profile) {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+    if args.lenient {
+        Day1::select_strictness(Strictness::Lenient).expect("nothing has been solved yet");
+    }
     if args.cookie {
-        util::clear_cookie();
+        session::clear_cookie();
     }
-    if args.all {
-        for day in 1..=25 {
-            if args.pull {
-                util::download_input(day);
+    if args.cookie_check {
+        match session::check_cookie() {
+            Ok(()) => println!("The session cookie for the {} profile works", args.profile),
+            Err(e) => {
+                eprintln!("{e}");
+                success = false;
             }
-            if let Some(solution1) = solve_part1(day) {
-                println!("Day {day}");
-                println!("  Part 1: {solution1}");
-                if let Some(solution2) = solve_part2(day) {
-                    println!("  Part 2: {solution2}");
-                }
+        }
+    }
+    if let Some(Command::New { day, title, pull }) = &args.command {
+        match scaffold::new_day(args.year, *day, title.as_deref(), *pull) {
+            Ok(path) => println!("Created {}", path.display()),
+            Err(e) => {
+                eprintln!("{e}");
+                std::process::exit(1);
             }
         }
+        return;
+    }
+    if let (true, Some(day)) = (args.watch, args.day) {
+        watch(&args, day);
     }
-    if let Some(day) = args.day {
-        if args.pull {
-            util::download_input(day);
+    let year = args.year;
+    if args.list {
+        list(year, args.format);
+    }
+    let load_ledger = || {
+        Ledger::load(year).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        })
+    };
+    if args.check {
+        success &= check(args.format, year, &load_ledger());
+    }
+    let mut ledger = args.record.then(load_ledger);
+    let mut records = vec![];
+    let source = source(&args);
+    if args.time {
+        success &= time(&args);
+    } else {
+        if args.all {
+            let mut solvers = vec![];
+            for solver in util::solvers_for(year) {
+                let day = solver.day;
+                if args.pull {
+                    if let Err(e) = util::download_input(year, day) {
+                        eprintln!("Day {day}");
+                        eprintln!("  {e}");
+                        success = false;
+                        continue;
+                    }
+                }
+                solvers.push(solver);
+            }
+            solve_days(&solvers, args.jobs, &source, |i, solutions| {
+                let day = solvers[i].day;
+                if args.format == Format::Text {
+                    if matches!(solutions[0].result, Err(AocError::Unsolved)) {
+                        return;
+                    }
+                    println!("Day {day}");
+                }
+                success &= output(&args, &mut records, "  ", day, &solutions);
+                record(&mut ledger, day, &solutions);
+            });
         }
-        if let Some(solution1) = solve_part1(day) {
-            println!("Part 1: {solution1}");
-            if let Some(solution2) = solve_part2(day) {
-                println!("Part 2: {solution2}");
+        if let Some(day) = args.day {
+            let Some(solver) = util::solver(year, day) else {
+                eprintln!("There is no solver for {year} day {day}");
+                std::process::exit(1);
+            };
+            if args.wait {
+                util::schedule::wait_for_unlock(year, day);
+            }
+            if args.pull || args.wait {
+                if let Err(e) = util::download_input(year, day) {
+                    eprintln!("{e}");
+                    std::process::exit(1);
+                }
+            }
+            if args.pull_examples {
+                match util::examples::download_examples(year, day) {
+                    Ok(examples) => {
+                        for (k, example) in examples.iter().enumerate() {
+                            let expected =
+                                [1, 2].map(|part| example.answers.get(part).unwrap_or("?"));
+                            status(
+                                args.format,
+                                format!(
+                                    "Example {}: {} lines, expecting {} and {}",
+                                    k + 1,
+                                    example.input.lines().count(),
+                                    expected[0],
+                                    expected[1]
+                                ),
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("{e}");
+                        std::process::exit(1);
+                    }
+                }
             }
-        } else {
-            println!("At least one solution must be implemented before it can be run");
+            solve_days(&[solver], args.jobs, &source, |_, solutions| {
+                if args.format == Format::Text
+                    && matches!(solutions[0].result, Err(AocError::Unsolved))
+                {
+                    println!("At least one solution must be implemented before it can be run");
+                    return;
+                }
+                success &= output(&args, &mut records, "", day, &solutions);
+                record(&mut ledger, day, &solutions);
+                if let Some(part) = args.submit {
+                    let part = part as usize;
+                    success &= submit(args.format, year, day, part, &solutions[part - 1].result);
+                }
+            });
+        }
+    }
+    if args.format != Format::Text {
+        print!("{}", output::render(args.format, &records));
+    }
+    if let Some(ledger) = ledger {
+        if let Err(e) = ledger.save() {
+            eprintln!("{e}");
+            success = false;
         }
     }
+    if !success {
+        std::process::exit(1);
+    }
 }
@@ -1,97 +1,441 @@
-use reqwest::blocking::Client;
+use std::any::Any;
 use std::fmt::Display;
-use std::fs::{remove_file, File};
-use std::io::{stdin, BufRead, Read, Write};
-use std::path::PathBuf;
-
-const YEAR: usize = 2023;
-const COOKIE_FILE: &str = ".cookie";
-
-// This might be useful for getting the current level
-// fn problem_page_url(day: usize) -> String {
-//     format!("https://adventofcode.com/{YEAR}/day/{day}")
-// }
-fn input_url(day: usize) -> String {
-    format!("https://adventofcode.com/{YEAR}/day/{day}/input")
-}
-// This will be useful if I ever decide to submit answers automagically
-// fn submit_url(day: usize) -> String {
-//     format!("https://adventofcode.com/{YEAR}/day/{day}/answer")
-// }
-fn input_file(day: usize) -> PathBuf {
-    format!("inputs/day{day}.txt").into()
-}
-
-/// Delete the cached cookie file
-pub fn clear_cookie() {
-    println!("Clearing the session cookie");
-    // Failing is fine, maybe the file doesn't exist
-    let _ = remove_file(COOKIE_FILE);
-}
-
-/// Get the session cookie from the cache, or query the user for it
-fn cookie() -> String {
-    let path: PathBuf = COOKIE_FILE.into();
-    let mut buffer = String::new();
-    if path.exists() {
-        let mut f = File::open(path).unwrap();
-        f.read_to_string(&mut buffer).unwrap();
+use std::fs::{create_dir_all, rename, File};
+use std::io::{stdin, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use http::Http;
+use std::sync::OnceLock;
+
+pub mod examples;
+pub mod grid;
+pub mod http;
+pub mod input;
+pub mod ledger;
+pub mod output;
+pub mod pool;
+pub mod scaffold;
+pub mod schedule;
+pub mod session;
+pub mod submit;
+pub mod timing;
+pub mod watch;
+
+/// The event to use when no year is given
+pub const DEFAULT_YEAR: usize = 2023;
+/// Inputs used to be cached directly in `inputs/`, back when there was only this year
+const LEGACY_YEAR: usize = 2023;
+const BASE_URL: &str = "https://adventofcode.com";
+
+fn problem_page_path(year: usize, day: usize) -> String {
+    format!("/{year}/day/{day}")
+}
+fn input_path(year: usize, day: usize) -> String {
+    format!("/{year}/day/{day}/input")
+}
+fn input_dir(year: usize) -> PathBuf {
+    format!("inputs/{year}").into()
+}
+/// Where the puzzle input of a day is cached
+pub fn input_file(year: usize, day: usize) -> PathBuf {
+    input_dir(year).join(format!("day{day}.txt"))
+}
+
+/// Everything that can go wrong while getting an answer for a day
+#[derive(Clone, Debug)]
+pub enum AocError {
+    /// The input did not look the way the parser expected. Lines and columns are zero-indexed.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The input could not be read from the cache or downloaded
+    MissingInput(String),
+    /// The part has not been implemented yet
+    Unsolved,
+    /// The solver ran to completion without finding an answer
+    Solver(String),
+    /// Talking to the Advent of Code website failed
+    Http(String),
+    /// A file other than the input could not be read or written
+    Io(String),
+    /// The command line or the environment asks for something that cannot be done
+    Usage(String),
+    /// The website did not accept the session cookie
+    SessionExpired,
+    /// The puzzle has not been released yet
+    Locked {
+        year: usize,
+        day: usize,
+        wait: std::time::Duration,
+    },
+}
+
+impl AocError {
+    /// A parse error at `column` of whatever line is being parsed.
+    /// The line number is filled in by [`AocError::on_line`] once it is known.
+    pub fn parse(column: usize, message: impl Display) -> AocError {
+        AocError::Parse {
+            line: 0,
+            column,
+            message: message.to_string(),
+        }
+    }
+    pub fn solver(message: impl Display) -> AocError {
+        AocError::Solver(message.to_string())
+    }
+    /// Attach a line number to a parse error. Other errors are left alone.
+    pub fn on_line(self, line: usize) -> AocError {
+        match self {
+            AocError::Parse {
+                column, message, ..
+            } => AocError::Parse {
+                line,
+                column,
+                message,
+            },
+            error => error,
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            // Humans count from 1
+            AocError::Parse {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "Parse error at line {}, column {}: {message}",
+                line + 1,
+                column + 1
+            ),
+            AocError::MissingInput(reason) => write!(f, "Missing input: {reason}"),
+            AocError::Unsolved => write!(f, "Not solved yet"),
+            AocError::Solver(reason) => write!(f, "Solver failed: {reason}"),
+            AocError::Http(reason) => write!(f, "Request failed: {reason}"),
+            AocError::Io(reason) => write!(f, "File error: {reason}"),
+            AocError::Usage(reason) => write!(f, "Cannot do that: {reason}"),
+            AocError::Locked { year, day, wait } => write!(
+                f,
+                "{year} day {day} unlocks in {} (use --wait to wait for it)",
+                schedule::countdown(*wait)
+            ),
+            AocError::SessionExpired => write!(
+                f,
+                "Session expired: log in again and replace the cookie (clear it with --cookie)"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+/// An [`AocError::Io`] for a file that could not be read or written
+fn io_error(path: &Path, e: impl Display) -> AocError {
+    AocError::Io(format!("{}: {e}", path.display()))
+}
+
+/// Parse every line of the input with `parse`, attaching line numbers to any errors
+pub fn parse_lines<'a, T>(input: &'a str, parse: impl Fn(&'a str) -> Result<T>) -> Result<Vec<T>> {
+    input::Input::new(input).parse_lines(parse)
+}
+
+/// The column that `field` starts at, assuming it was sliced out of `line`
+pub fn column_of(line: &str, field: &str) -> usize {
+    let offset = (field.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    if offset <= line.len() {
+        offset
     } else {
-        println!("Please paste the contents of the session cookie: ");
-        stdin().read_line(&mut buffer).unwrap();
-        let mut f = File::create(path).unwrap();
-        f.write_all(buffer.as_bytes()).unwrap();
+        0
     }
-    buffer.trim().into()
+}
+
+/// Parse `field`, which was sliced out of `line`, reporting where it was if that fails
+pub fn parse_field<T>(line: &str, field: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    field
+        .parse()
+        .map_err(|e| AocError::parse(column_of(line, field), format!("{e} ({field:?})")))
+}
+
+/// Strip the tags out of some HTML and decode the entities the puzzle pages use
+fn html_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Write a file by renaming a finished temporary file over it,
+/// so that an interrupted write never leaves a truncated file behind
+fn write_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    let mut file = File::create(&temporary)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    rename(&temporary, path)
 }
 
 /// Get the raw input from the Advent of Code website
-pub fn download_input(day: usize) -> Vec<String> {
-    println!("Downloading the input for day {day}");
-    // Let it fail if there's a failure
-    let raw_input = Client::new()
-        .get(input_url(day))
-        .header("Cookie", format!("session={}", cookie()))
-        .send()
-        .unwrap()
-        .text()
-        .unwrap();
+pub fn download_input(year: usize, day: usize) -> Result<String> {
+    schedule::ensure_unlocked(year, day)?;
+    eprintln!("Downloading the input for {year} day {day}");
+    let raw_input = Http::shared().get(&input_path(year, day), &session::cookie()?)?;
     // Write the input to a file for later usage
-    let mut file = File::create(input_file(day)).unwrap();
-    file.write_all(raw_input.as_bytes()).unwrap();
-    raw_input.lines().map(String::from).collect()
+    let path = input_file(year, day);
+    create_dir_all(input_dir(year))
+        .and_then(|_| write_atomic(&path, &raw_input))
+        .map_err(|e| io_error(&path, e))?;
+    Ok(raw_input)
+}
+
+/// Read an input file
+fn read_input(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path)
+        .map_err(|e| AocError::MissingInput(format!("{}: {e}", path.display())))
+}
+
+/// Where a solver gets its input from
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum InputSource {
+    /// The real puzzle input, from the cache or downloaded
+    #[default]
+    Cached,
+    /// Some other file, so that the cached input never needs to be edited
+    File(PathBuf),
+    /// Whatever is piped in. It is read once and reused for both parts.
+    Stdin,
+    /// The `K`th example from the puzzle page, counting from 1
+    Example(usize),
+}
+
+impl InputSource {
+    /// Read the input for a day
+    pub fn read(&self, year: usize, day: usize) -> Result<String> {
+        match self {
+            InputSource::Cached => get_input(year, day),
+            InputSource::File(path) => read_input(path),
+            InputSource::Stdin => {
+                static STDIN: OnceLock<std::io::Result<String>> = OnceLock::new();
+                let read_all = || std::io::read_to_string(stdin().lock());
+                match STDIN.get_or_init(read_all) {
+                    Ok(input) => Ok(input.clone()),
+                    Err(e) => Err(AocError::MissingInput(format!("stdin: {e}"))),
+                }
+            }
+            InputSource::Example(k) => {
+                let path = examples::example_file(year, day, *k);
+                if !path.exists() {
+                    return Err(AocError::MissingInput(format!(
+                        "there is no example {k} for {year} day {day}, try --pull-examples"
+                    )));
+                }
+                read_input(&path)
+            }
+        }
+    }
+}
+
+/// Where the cached input of a day is, if it has been downloaded. An input cached where older
+/// versions kept it is moved to [`input_file`] first.
+pub fn cached_input(year: usize, day: usize) -> Result<Option<PathBuf>> {
+    let path = input_file(year, day);
+    let legacy_path = PathBuf::from(format!("inputs/day{day}.txt"));
+    if year == LEGACY_YEAR && !path.exists() && legacy_path.exists() {
+        create_dir_all(input_dir(year))
+            .and_then(|_| rename(&legacy_path, &path))
+            .map_err(|e| AocError::MissingInput(format!("{}: {e}", legacy_path.display())))?;
+    }
+    Ok(path.exists().then_some(path))
 }
 
 /// Get the input from the cache, or download it if necessary
-pub fn get_input(day: usize) -> Vec<String> {
-    let path = input_file(day);
-    if path.exists() {
-        let file = File::open(path).unwrap();
-        std::io::BufReader::new(file)
-            .lines()
-            .map(Result::unwrap)
-            .collect()
-    } else {
-        download_input(day)
+pub fn get_input(year: usize, day: usize) -> Result<String> {
+    match cached_input(year, day)? {
+        Some(path) => read_input(&path),
+        None => download_input(year, day),
     }
 }
 
+/// The parsed input of any day, as the registry passes it from parsing to solving
+pub type ParsedInput = Box<dyn Any + Send + Sync>;
+
 pub trait DaySolver<T>
 where
     T: Display,
 {
+    /// What the input is parsed into. It is parsed once and shared by both parts.
+    type Parsed: Send + Sync + 'static;
+    /// Which of part 1 and part 2 have been implemented
+    const IMPLEMENTED: [bool; 2] = [true, true];
+    /// Parse the raw input
+    fn parse(input: &str) -> Result<Self::Parsed>;
     /// The implemented solution to part 1
-    fn part1(input: Vec<String>) -> Option<T>;
+    fn part1(input: &Self::Parsed) -> Result<T>;
     /// The implemented solution to part 2
-    fn part2(input: Vec<String>) -> Option<T>;
-    /// Get the input for the day, call part1, and cast the result to a String
-    fn solve_part1(day: usize) -> Option<String> {
-        let input = get_input(day);
+    fn part2(input: &Self::Parsed) -> Result<T>;
+    /// Call parse, and box the result so that it can be stored without knowing its type
+    fn parse_any(input: &str) -> Result<ParsedInput> {
+        Ok(Box::new(Self::parse(input)?))
+    }
+    /// Call part1 on an input parsed by [`parse_any`](Self::parse_any), and cast the result to a String
+    fn run_part1(input: &ParsedInput) -> Result<String> {
+        let input = input.downcast_ref().expect("parsed by the same day");
         Self::part1(input).map(|solution| format!("{}", solution))
     }
-    /// Get the input for the day, call part2, and cast the result to a String
-    fn solve_part2(day: usize) -> Option<String> {
-        let input = get_input(day);
+    /// Call part2 on an input parsed by [`parse_any`](Self::parse_any), and cast the result to a String
+    fn run_part2(input: &ParsedInput) -> Result<String> {
+        let input = input.downcast_ref().expect("parsed by the same day");
         Self::part2(input).map(|solution| format!("{}", solution))
     }
 }
+
+/// A registered day, as declared by [`days!`](crate::days)
+#[derive(Clone, Copy)]
+pub struct Solver {
+    pub year: usize,
+    pub day: usize,
+    /// The name of the type implementing [`DaySolver`], like "Day1"
+    pub name: &'static str,
+    /// The title of the puzzle
+    pub title: &'static str,
+    pub implemented: [bool; 2],
+    pub parse: fn(&str) -> Result<ParsedInput>,
+    pub part1: fn(&ParsedInput) -> Result<String>,
+    pub part2: fn(&ParsedInput) -> Result<String>,
+}
+
+impl Solver {
+    /// The solution to part 1 or part 2, if it has been implemented
+    pub fn part(&self, part: usize) -> Option<fn(&ParsedInput) -> Result<String>> {
+        match part {
+            1 if self.implemented[0] => Some(self.part1),
+            2 if self.implemented[1] => Some(self.part2),
+            _ => None,
+        }
+    }
+
+    /// Get the input and parse it
+    pub fn load(&self, source: &InputSource) -> Result<ParsedInput> {
+        (self.parse)(&source.read(self.year, self.day)?)
+    }
+
+    /// Solve part 1 or part 2 of the day from the parsed input
+    pub fn solve(&self, part: usize, input: &ParsedInput) -> Result<String> {
+        let run = self.part(part).ok_or(AocError::Unsolved)?;
+        run(input)
+    }
+}
+
+/// Every registered day of every year, in order
+pub fn solvers() -> impl Iterator<Item = &'static Solver> {
+    crate::SOLVERS.iter().flat_map(|year| year.iter())
+}
+
+/// Every registered day of one year, in order
+pub fn solvers_for(year: usize) -> impl Iterator<Item = &'static Solver> {
+    solvers().filter(move |solver| solver.year == year)
+}
+
+/// Every year with at least one registered day
+pub fn years() -> impl Iterator<Item = usize> {
+    crate::SOLVERS
+        .iter()
+        .filter_map(|year| year.first().map(|solver| solver.year))
+}
+
+/// The registered solver for a day, if there is one
+pub fn solver(year: usize, day: usize) -> Option<&'static Solver> {
+    solvers().find(|solver| solver.year == year && solver.day == day)
+}
+
+/// Declare the day modules and register their [`DaySolver`]s in `SOLVERS`, grouped by year.
+/// Adding a day only requires a new line here, instead of a new arm in every dispatch `match`.
+///
+/// Days of the year this crate was started for live at the crate root.
+/// Other years keep their days in a directory named after the year, like `src/year2015/day1.rs`:
+/// ```text
+/// days! {
+///     2023 {
+///         1 => day1::Day1, "Trebuchet?!";
+///     }
+///     2015 in year2015 {
+///         1 => day1::Day1, "Not Quite Lisp";
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! days {
+    ($($year:literal $(in $dir:ident)? {
+        $($day:literal => $module:ident::$solver:ident, $title:literal;)*
+    })*) => {
+        $($crate::days!(@modules $($dir)? { $($module)* });)*
+
+        pub static SOLVERS: &[&[$crate::util::Solver]] = &[$(
+            $crate::days!(@solvers $year, $($dir,)? { $($day => $module::$solver, $title;)* })
+        ),*];
+    };
+    (@modules { $($module:ident)* }) => {
+        $(pub mod $module;)*
+    };
+    (@modules $dir:ident { $($module:ident)* }) => {
+        pub mod $dir {
+            $(pub mod $module;)*
+        }
+    };
+    (@solvers $year:literal, $dir:ident, { $($day:literal => $module:ident::$solver:ident, $title:literal;)* }) => {
+        $crate::days!(@list $year, { $($day => $dir::$module::$solver as $solver, $title;)* })
+    };
+    (@solvers $year:literal, { $($day:literal => $module:ident::$solver:ident, $title:literal;)* }) => {
+        $crate::days!(@list $year, { $($day => $module::$solver as $solver, $title;)* })
+    };
+    (@list $year:literal, { $($day:literal => $($path:ident)::+ as $solver:ident, $title:literal;)* }) => {
+        &[$(
+            $crate::util::Solver {
+                year: $year,
+                day: $day,
+                name: stringify!($solver),
+                title: $title,
+                implemented: <$($path)::+ as $crate::util::DaySolver<_>>::IMPLEMENTED,
+                parse: <$($path)::+ as $crate::util::DaySolver<_>>::parse_any,
+                part1: <$($path)::+ as $crate::util::DaySolver<_>>::run_part1,
+                part2: <$($path)::+ as $crate::util::DaySolver<_>>::run_part2,
+            },
+        )*]
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn test_solvers_are_in_order() {
+        for year in years() {
+            let days: Vec<usize> = solvers_for(year).map(|solver| solver.day).collect();
+            assert_eq!(days, (1..=days.len()).collect::<Vec<_>>());
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::PathBuf;
+
+use super::http::Http;
+use super::ledger::Answers;
+use super::schedule;
+use super::session::cookie;
+use super::{html_text, input_dir, io_error, problem_page_path, AocError, Result};
+
+/// An example input from the puzzle description, with the answers it is supposed to give
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Example {
+    pub input: String,
+    pub answers: Answers,
+}
+
+pub fn examples_dir(year: usize) -> PathBuf {
+    input_dir(year).join("examples")
+}
+
+/// The input file of the `k`th example of a day, counting from 1
+pub fn example_file(year: usize, day: usize, k: usize) -> PathBuf {
+    examples_dir(year).join(format!("day{day}-{k}.txt"))
+}
+
+/// The file holding the expected answers of the `k`th example of a day
+pub fn example_answers_file(year: usize, day: usize, k: usize) -> PathBuf {
+    examples_dir(year).join(format!("day{day}-{k}.json"))
+}
+
+/// Everything between each `start` and the following `end`
+fn between<'a>(text: &'a str, start: &'a str, end: &'a str) -> impl Iterator<Item = &'a str> {
+    text.split(start)
+        .skip(1)
+        .filter_map(move |chunk| chunk.split_once(end).map(|(inside, _)| inside))
+}
+
+/// Pull the examples out of a puzzle page.
+///
+/// Each part of the puzzle is described in its own `<article>`, which usually ends by
+/// highlighting the answer to its examples. That answer is attached to the last example seen so
+/// far, since part 2 often reuses the example from part 1.
+pub fn extract_examples(html: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = vec![];
+    for (i, article) in between(html, "<article", "</article>").take(2).enumerate() {
+        for block in between(article, "<pre><code>", "</code></pre>") {
+            examples.push(Example {
+                input: html_text(block),
+                answers: Answers::default(),
+            });
+        }
+        let answer = between(article, "<code><em>", "</em></code>").last();
+        if let (Some(answer), Some(example)) = (answer, examples.last_mut()) {
+            example.answers.set(i + 1, &html_text(answer));
+        }
+    }
+    examples
+}
+
+/// The title of the puzzle, like "Gear Ratios"
+pub fn extract_title(html: &str) -> Option<String> {
+    let heading = between(html, "<h2>--- ", " ---</h2>").next()?;
+    let (_, title) = heading.split_once(": ")?;
+    Some(html_text(title))
+}
+
+/// Download the puzzle page for a day
+pub fn download_puzzle_page(year: usize, day: usize) -> Result<String> {
+    schedule::ensure_unlocked(year, day)?;
+    eprintln!("Downloading the puzzle page for {year} day {day}");
+    Http::shared().get(&problem_page_path(year, day), &cookie()?)
+}
+
+/// Save examples in the examples directory, replacing any that were already saved
+pub fn save_examples(year: usize, day: usize, examples: &[Example]) -> Result<()> {
+    let io_error = |e: std::io::Error| AocError::Io(format!("saving examples: {e}"));
+    create_dir_all(examples_dir(year)).map_err(io_error)?;
+    for (k, example) in examples.iter().enumerate() {
+        write(example_file(year, day, k + 1), &example.input).map_err(io_error)?;
+        if example.answers != Answers::default() {
+            let answers = serde_json::to_string_pretty(&example.answers)
+                .map_err(|e| AocError::Io(format!("saving examples: {e}")))?;
+            write(example_answers_file(year, day, k + 1), answers + "\n").map_err(io_error)?;
+        }
+    }
+    Ok(())
+}
+
+/// Download the puzzle page for a day and save its examples in the examples directory
+pub fn download_examples(year: usize, day: usize) -> Result<Vec<Example>> {
+    let examples = extract_examples(&download_puzzle_page(year, day)?);
+    save_examples(year, day, &examples)?;
+    Ok(examples)
+}
+
+/// The saved examples of a day, in order. Examples without saved answers have no answers.
+pub fn load_examples(year: usize, day: usize) -> Result<Vec<Example>> {
+    let mut examples = vec![];
+    for k in 1.. {
+        let path = example_file(year, day, k);
+        if !path.exists() {
+            break;
+        }
+        let input = read_to_string(&path).map_err(|e| io_error(&path, e))?;
+        let answers_path = example_answers_file(year, day, k);
+        let answers = if answers_path.exists() {
+            let json = read_to_string(&answers_path).map_err(|e| io_error(&answers_path, e))?;
+            serde_json::from_str(&json).map_err(|e| io_error(&answers_path, e))?
+        } else {
+            Answers::default()
+        };
+        examples.push(Example { input, answers });
+    }
+    Ok(examples)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 3: Gear Ratios ---</h2><p>Here is an example engine schematic:</p>
+<pre><code>467..114..
+...*......
+..35..633.
+</code></pre>
+<p>In this schematic, two numbers are <em>not</em> part numbers. Every other number is adjacent to a symbol, so the sum is <code><em>4361</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>A <code>*</code> is a gear. Adding up all of the gear ratios produces <code><em>467835</em></code>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_extract_examples() {
+        let examples = extract_examples(PAGE);
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].input, "467..114..\n...*......\n..35..633.\n");
+        assert_eq!(examples[0].answers.get(1), Some("4361"));
+        assert_eq!(examples[0].answers.get(2), Some("467835"));
+    }
+
+    #[test]
+    fn test_extract_title() {
+        assert_eq!(extract_title(PAGE).as_deref(), Some("Gear Ratios"));
+    }
+
+    #[test]
+    fn test_separate_examples() {
+        let page = r#"<article><pre><code>1abc2
+</code></pre><p>Adding these together produces <code><em>142</em></code>.</p></article>
+<article><pre><code>two1nine
+<em>eight</em>wothree
+</code></pre><p>Adding these together produces <code><em>281</em></code>.</p></article>"#;
+        let examples = extract_examples(page);
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].answers.get(1), Some("142"));
+        assert_eq!(examples[0].answers.get(2), None);
+        assert_eq!(examples[1].input, "two1nine\neightwothree\n");
+        assert_eq!(examples[1].answers.get(1), None);
+        assert_eq!(examples[1].answers.get(2), Some("281"));
+    }
+
+    #[test]
+    fn test_entities() {
+        assert_eq!(html_text("a &lt;&amp;&gt; <em>b</em>"), "a <&> b");
+    }
+}
@@ -0,0 +1,236 @@
+//! Rectangular grids of tiles, for the puzzles that take place on a map.
+
+use std::ops::{Index, IndexMut};
+
+use super::input::{GridView, Input};
+use super::{AocError, Result};
+
+/// A position in a grid, as `(x, y)` with `(0, 0)` in the top left corner
+pub type Pos = (usize, usize);
+
+/// A compass direction. North is up, towards smaller y.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Dir {
+    N,
+    E,
+    S,
+    W,
+}
+
+/// Which way to turn, relative to the direction being faced
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Turn {
+    Left,
+    Right,
+    Around,
+}
+
+impl Dir {
+    /// Clockwise, starting from north
+    pub const ALL: [Dir; 4] = [Dir::N, Dir::E, Dir::S, Dir::W];
+
+    /// A distinct number from 0 to 3, for indexing arrays by direction
+    pub fn index(self) -> usize {
+        self as usize
+    }
+    pub fn is_vertical(self) -> bool {
+        self == Dir::N || self == Dir::S
+    }
+    pub fn turn(self, turn: Turn) -> Dir {
+        let quarters = match turn {
+            Turn::Right => 1,
+            Turn::Around => 2,
+            Turn::Left => 3,
+        };
+        Dir::ALL[(self.index() + quarters) % 4]
+    }
+    /// The change in `(x, y)` from taking one step this way
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Dir::N => (0, -1),
+            Dir::E => (1, 0),
+            Dir::S => (0, 1),
+            Dir::W => (-1, 0),
+        }
+    }
+    /// Take one step on an unbounded plane
+    pub fn step(self, (x, y): (isize, isize)) -> (isize, isize) {
+        let (dx, dy) = self.offset();
+        (x + dx, y + dy)
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    tiles: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, tile: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            tiles: vec![tile; width * height],
+            width,
+            height,
+        }
+    }
+    /// Parse every byte of the input with `tile`, which returns `None` for bytes that aren't tiles
+    pub fn parse(input: &str, tile: impl FnMut(u8) -> Option<T>) -> Result<Self> {
+        Self::from_view(&Input::new(input).grid()?, tile)
+    }
+    /// Like [`parse`](Self::parse), for input that has already been checked to be rectangular
+    pub fn from_view(view: &GridView, mut tile: impl FnMut(u8) -> Option<T>) -> Result<Self> {
+        let mut tiles = Vec::with_capacity(view.width() * view.height());
+        for (y, row) in view.rows().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                tiles.push(tile(c).ok_or_else(|| {
+                    AocError::parse(x, format!("{:?} is not a tile", char::from(c)))
+                        .on_line(view.first_line() + y)
+                })?);
+            }
+        }
+        Ok(Grid {
+            tiles,
+            width: view.width(),
+            height: view.height(),
+        })
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.tiles[pos.1 * self.width + pos.0])
+    }
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.tiles[pos.1 * self.width + pos.0])
+        } else {
+            None
+        }
+    }
+    /// The tile at a position on an infinite plane, tiled with copies of this grid
+    pub fn get_wrapping(&self, (x, y): (isize, isize)) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self[(x, y)]
+    }
+    /// The position one step away in `dir`, if it is still in the grid
+    pub fn step(&self, (x, y): Pos, dir: Dir) -> Option<Pos> {
+        let (dx, dy) = dir.offset();
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(pos).then_some(pos)
+    }
+    /// The positions directly north, east, south and west, that are in the grid
+    pub fn neighbors(&self, pos: Pos) -> impl Iterator<Item = (Dir, Pos)> + '_ {
+        Dir::ALL
+            .into_iter()
+            .filter_map(move |dir| Some((dir, self.step(pos, dir)?)))
+    }
+    /// The positions around `pos` that are in the grid, including the diagonals
+    pub fn adjacent(&self, (x, y): Pos) -> impl Iterator<Item = Pos> + '_ {
+        (y.saturating_sub(1)..=y + 1)
+            .flat_map(move |yy| (x.saturating_sub(1)..=x + 1).map(move |xx| (xx, yy)))
+            .filter(move |&pos| pos != (x, y) && self.contains(pos))
+    }
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.tiles[y * self.width..(y + 1) * self.width]
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.tiles.chunks(self.width)
+    }
+    /// Every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+    /// Every tile and its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.tiles.iter())
+    }
+    /// The position of the first tile that matches, row by row
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find(|(_, tile)| predicate(tile))
+            .map(|(pos, _)| pos)
+    }
+    /// Swap the rows and the columns
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            tiles: (0..self.width)
+                .flat_map(|x| (0..self.height).map(move |y| self[(x, y)].clone()))
+                .collect(),
+            width: self.height,
+            height: self.width,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): Pos) -> &T {
+        assert!(x < self.width, "x = {x} is outside of the grid");
+        &self.tiles[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (x, y): Pos) -> &mut T {
+        assert!(x < self.width, "x = {x} is outside of the grid");
+        &mut self.tiles[y * self.width + x]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_turn() {
+        assert_eq!(Dir::N.turn(Turn::Right), Dir::E);
+        assert_eq!(Dir::N.turn(Turn::Left), Dir::W);
+        assert_eq!(Dir::W.turn(Turn::Right), Dir::N);
+        assert_eq!(Dir::E.turn(Turn::Around), Dir::W);
+    }
+
+    #[test]
+    fn test_grid() {
+        let grid = Grid::parse("123\n456\n", |c| c.is_ascii_digit().then(|| c - b'0')).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(*grid.get_wrapping((-1, 2)), 3);
+        assert_eq!(grid.step((0, 0), Dir::N), None);
+        assert_eq!(grid.step((0, 0), Dir::S), Some((0, 1)));
+        assert_eq!(
+            grid.neighbors((1, 0)).collect::<Vec<_>>(),
+            vec![(Dir::E, (2, 0)), (Dir::S, (1, 1)), (Dir::W, (0, 0))]
+        );
+        assert_eq!(grid.adjacent((0, 0)).count(), 3);
+        assert_eq!(grid.adjacent((1, 1)).count(), 5);
+        assert_eq!(grid.transpose().row(2), &[3, 6]);
+        assert_eq!(grid.position(|&n| n > 4), Some((1, 1)));
+
+        let error = Grid::parse("12\n3x\n", |c| c.is_ascii_digit().then_some(c)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            AocError::parse(1, "'x' is not a tile")
+                .on_line(1)
+                .to_string()
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use std::sync::{Mutex, OnceLock};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use reqwest::blocking::{Client, RequestBuilder};
+use reqwest::StatusCode;
+
+use super::{AocError, Result, BASE_URL};
+
+/// Advent of Code asks automated tools to say who they are
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (github.com/dchiquito/advent-of-code-2023)"
+);
+/// Never send requests closer together than this
+const MIN_INTERVAL: Duration = Duration::from_secs(1);
+const RETRIES: u32 = 3;
+const BACKOFF: Duration = Duration::from_secs(2);
+
+/// A client for the Advent of Code website, shared by everything that talks to it
+pub struct Http {
+    client: Client,
+    base_url: String,
+    min_interval: Duration,
+    backoff: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl Http {
+    pub fn new(base_url: &str) -> Http {
+        Http::with_timing(base_url, MIN_INTERVAL, BACKOFF)
+    }
+
+    fn with_timing(base_url: &str, min_interval: Duration, backoff: Duration) -> Http {
+        let client = Client::builder()
+            .user_agent(USER_AGENT)
+            .build()
+            .expect("the HTTP client has a valid configuration");
+        Http {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            min_interval,
+            backoff,
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// The client for the real website
+    pub fn shared() -> &'static Http {
+        static HTTP: OnceLock<Http> = OnceLock::new();
+        HTTP.get_or_init(|| Http::new(BASE_URL))
+    }
+
+    /// GET a page, like `/2023/day/1/input`, as the user with the `session` cookie
+    pub fn get(&self, path: &str, session: &str) -> Result<String> {
+        self.send(|| self.client.get(self.url(path)), session, RETRIES)
+    }
+
+    /// POST a form to a page as the user with the `session` cookie. This is never retried,
+    /// because the server may have acted on the form before it failed.
+    pub fn post_form(&self, path: &str, session: &str, form: &[(&str, &str)]) -> Result<String> {
+        self.send(|| self.client.post(self.url(path)).form(form), session, 0)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url)
+    }
+
+    /// Wait until enough time has passed since the last request
+    fn throttle(&self) {
+        let mut last_request = self.last_request.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(last) = *last_request {
+            let next = last + self.min_interval;
+            let now = Instant::now();
+            if next > now {
+                sleep(next - now);
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+
+    /// Send a request, retrying up to `retries` times with exponential backoff when the server or
+    /// connection fails
+    fn send(
+        &self,
+        request: impl Fn() -> RequestBuilder,
+        session: &str,
+        retries: u32,
+    ) -> Result<String> {
+        let mut attempt = 0;
+        loop {
+            self.throttle();
+            let response = request()
+                .header("Cookie", format!("session={session}"))
+                .send()
+                .and_then(|response| {
+                    let status = response.status();
+                    response.text().map(|body| (status, body))
+                });
+            let retry = match response {
+                Ok((status, body)) => match check_response(status, &body) {
+                    Err(AocError::Http(reason)) if status.is_server_error() => reason,
+                    checked => return checked.map(|_| body),
+                },
+                Err(e) => e.to_string(),
+            };
+            if attempt == retries {
+                return Err(AocError::Http(retry));
+            }
+            sleep(self.backoff * 2u32.pow(attempt));
+            attempt += 1;
+        }
+    }
+}
+
+/// Make sure a response is not an error, even one that was sent with a successful status
+fn check_response(status: StatusCode, body: &str) -> Result<()> {
+    if body.contains("Please log in") || body.contains("please identify yourself") {
+        return Err(AocError::SessionExpired);
+    }
+    if status == StatusCode::NOT_FOUND && body.contains("before it unlocks") {
+        return Err(AocError::Http(
+            "the puzzle has not unlocked yet".to_string(),
+        ));
+    }
+    if !status.is_success() {
+        let first_line = body.lines().next().unwrap_or_default();
+        return Err(AocError::Http(format!("{status}: {first_line}")));
+    }
+    Ok(())
+}
+
+/// A stand-in for the website, for testing
+#[cfg(test)]
+pub(super) mod stand_in {
+    use super::Http;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    /// A request that the stand-in received
+    #[derive(Debug, Default)]
+    pub struct Request {
+        pub method: String,
+        pub path: String,
+        pub cookie: String,
+        pub user_agent: String,
+        pub body: String,
+    }
+
+    /// Answer one request for each of `responses` with that status and body, then return what
+    /// the requests were. The client it comes with does not wait between requests.
+    pub fn serve(responses: Vec<(u16, String)>) -> (Http, thread::JoinHandle<Vec<Request>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+            for (status, html) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = Request::default();
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut words = request_line.split(' ');
+                request.method = words.next().unwrap().to_string();
+                request.path = words.next().unwrap().to_string();
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    let header = header.trim_end();
+                    if header.is_empty() {
+                        break;
+                    }
+                    let (name, value) = header.split_once(": ").unwrap();
+                    match name.to_ascii_lowercase().as_str() {
+                        "content-length" => content_length = value.parse().unwrap(),
+                        "cookie" => request.cookie = value.to_string(),
+                        "user-agent" => request.user_agent = value.to_string(),
+                        _ => {}
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                request.body = String::from_utf8(body).unwrap();
+                let response = format!(
+                    "HTTP/1.1 {status} Whatever\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{html}",
+                    html.len()
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+                requests.push(request);
+            }
+            requests
+        });
+        (
+            Http::with_timing(&url, Duration::ZERO, Duration::from_millis(1)),
+            handle,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::stand_in::serve;
+    use super::*;
+
+    #[test]
+    fn test_get() {
+        let (http, server) = serve(vec![(200, "1abc2\n".to_string())]);
+        assert_eq!(http.get("/2023/day/1/input", "abc123").unwrap(), "1abc2\n");
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2023/day/1/input");
+        assert_eq!(requests[0].cookie, "session=abc123");
+        assert_eq!(requests[0].user_agent, USER_AGENT);
+    }
+
+    #[test]
+    fn test_session_expired() {
+        let (http, server) = serve(vec![(
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".to_string(),
+        )]);
+        assert!(matches!(
+            http.get("/2023/day/1/input", "stale"),
+            Err(AocError::SessionExpired)
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_retry() {
+        let (http, server) = serve(vec![
+            (500, "Internal Server Error\n".to_string()),
+            (503, "Service Unavailable\n".to_string()),
+            (200, "1abc2\n".to_string()),
+        ]);
+        assert_eq!(http.get("/2023/day/1/input", "abc123").unwrap(), "1abc2\n");
+        assert_eq!(server.join().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_no_retry_for_posts() {
+        let (http, server) = serve(vec![(500, "Internal Server Error\n".to_string())]);
+        // A retry would find the server gone and fail to connect instead
+        match http.post_form("/2023/day/1/answer", "abc123", &[("level", "1")]) {
+            Err(AocError::Http(reason)) => assert!(reason.starts_with("500")),
+            other => panic!("expected an HTTP error, got {other:?}"),
+        }
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_no_retry_for_client_errors() {
+        let (http, server) = serve(vec![(404, "Not Found\n".to_string())]);
+        match http.get("/2023/day/26/input", "abc123") {
+            Err(AocError::Http(reason)) => assert!(reason.starts_with("404")),
+            other => panic!("expected an HTTP error, got {other:?}"),
+        }
+        server.join().unwrap();
+    }
+}
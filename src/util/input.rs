@@ -0,0 +1,203 @@
+//! Views of the puzzle input that borrow it instead of copying it, so that parsers only pay for
+//! the structures they actually build.
+
+use super::{AocError, Result};
+
+/// Some of the puzzle input, and the line of the whole input that it starts on
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Input<'a> {
+    text: &'a str,
+    first_line: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Input {
+            text,
+            first_line: 0,
+        }
+    }
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.text.as_bytes()
+    }
+    /// The line of the whole input that this part of it starts on
+    pub fn first_line(&self) -> usize {
+        self.first_line
+    }
+    pub fn lines(&self) -> std::str::Lines<'a> {
+        self.text.lines()
+    }
+    /// The lines, paired with their line number in the whole input
+    pub fn numbered_lines(&self) -> impl Iterator<Item = (usize, &'a str)> {
+        (self.first_line..).zip(self.text.lines())
+    }
+    pub fn line(&self, y: usize) -> Option<&'a str> {
+        self.text.lines().nth(y)
+    }
+    /// Parse every line with `parse`, attaching line numbers to any errors
+    pub fn parse_lines<T>(&self, parse: impl Fn(&'a str) -> Result<T>) -> Result<Vec<T>> {
+        self.numbered_lines()
+            .map(|(y, line)| parse(line).map_err(|e| e.on_line(y)))
+            .collect()
+    }
+    /// The blocks of lines separated by blank lines
+    pub fn paragraphs(&self) -> Paragraphs<'a> {
+        Paragraphs {
+            rest: self.text,
+            line: self.first_line,
+        }
+    }
+    /// View the lines as the rows of a grid of bytes, which must all be the same width
+    pub fn grid(&self) -> Result<GridView<'a>> {
+        let rows: Vec<&[u8]> = self.lines().map(str::as_bytes).collect();
+        let width = rows
+            .first()
+            .filter(|row| !row.is_empty())
+            .ok_or_else(|| AocError::parse(0, "the input is empty").on_line(self.first_line))?
+            .len();
+        if let Some(y) = rows.iter().position(|row| row.len() != width) {
+            return Err(AocError::parse(0, "this row is not as wide as the others")
+                .on_line(self.first_line + y));
+        }
+        Ok(GridView {
+            rows,
+            width,
+            first_line: self.first_line,
+        })
+    }
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim_end_matches(['\r', '\n']).is_empty()
+}
+
+/// Iterator over the paragraphs of an [`Input`]
+pub struct Paragraphs<'a> {
+    rest: &'a str,
+    line: usize,
+}
+
+impl<'a> Iterator for Paragraphs<'a> {
+    type Item = Input<'a>;
+
+    fn next(&mut self) -> Option<Input<'a>> {
+        while let Some(blank) = self
+            .rest
+            .split_inclusive('\n')
+            .next()
+            .filter(|l| is_blank(l))
+        {
+            self.rest = &self.rest[blank.len()..];
+            self.line += 1;
+        }
+        if self.rest.is_empty() {
+            return None;
+        }
+        let first_line = self.line;
+        let mut len = 0;
+        for line in self.rest.split_inclusive('\n') {
+            if is_blank(line) {
+                break;
+            }
+            len += line.len();
+            self.line += 1;
+        }
+        let (text, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Some(Input { text, first_line })
+    }
+}
+
+/// The lines of an [`Input`] as a rectangle of bytes, indexed by `(x, y)`
+#[derive(Clone, Debug)]
+pub struct GridView<'a> {
+    rows: Vec<&'a [u8]>,
+    width: usize,
+    first_line: usize,
+}
+
+impl<'a> GridView<'a> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+    /// The line of the whole input that the first row is on
+    pub fn first_line(&self) -> usize {
+        self.first_line
+    }
+    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+        self.rows.get(y).and_then(|row| row.get(x)).copied()
+    }
+    pub fn row(&self, y: usize) -> &'a [u8] {
+        self.rows[y]
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &'a [u8]> + '_ {
+        self.rows.iter().copied()
+    }
+    /// The first tile that is `tile`, reading row by row
+    pub fn position(&self, tile: u8) -> Option<(usize, usize)> {
+        self.rows
+            .iter()
+            .enumerate()
+            .find_map(|(y, row)| row.iter().position(|t| *t == tile).map(|x| (x, y)))
+    }
+    /// Check that every tile is one of `allowed`, reporting where the first one that isn't is
+    pub fn check_tiles(&self, allowed: &[u8]) -> Result<()> {
+        for (y, row) in self.rows.iter().enumerate() {
+            if let Some(x) = row.iter().position(|t| !allowed.contains(t)) {
+                return Err(AocError::parse(
+                    x,
+                    format!(
+                        "{:?} is not one of {:?}",
+                        char::from(row[x]),
+                        String::from_utf8_lossy(allowed)
+                    ),
+                )
+                .on_line(self.first_line + y));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_paragraphs() {
+        let input = Input::new("a\nb\n\nc\r\n\r\n\nd\ne\n");
+        let paragraphs: Vec<_> = input
+            .paragraphs()
+            .map(|p| (p.first_line(), p.lines().collect::<Vec<_>>()))
+            .collect();
+        assert_eq!(
+            paragraphs,
+            vec![(0, vec!["a", "b"]), (3, vec!["c"]), (6, vec!["d", "e"])]
+        );
+        assert_eq!(Input::new("\n\n").paragraphs().count(), 0);
+    }
+
+    #[test]
+    fn test_grid() {
+        let grid = Input::new("#..\n.#.\n").grid().unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(1, 1), Some(b'#'));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.position(b'#'), Some((0, 0)));
+        assert!(grid.check_tiles(b"#.").is_ok());
+
+        let paragraph = Input::new("#.\n\n#.\n.").paragraphs().nth(1).unwrap();
+        assert_eq!(
+            paragraph.grid().unwrap_err().to_string(),
+            AocError::parse(0, "this row is not as wide as the others")
+                .on_line(3)
+                .to_string()
+        );
+    }
+}
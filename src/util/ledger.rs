@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{io_error, Result};
+
+const LEDGER_DIR: &str = "answers";
+
+fn ledger_file(year: usize) -> PathBuf {
+    PathBuf::from(LEDGER_DIR).join(format!("{year}.json"))
+}
+
+/// The accepted answers to both parts of a day
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+impl Answers {
+    /// The answer to part 1 or part 2, if it is known
+    pub fn get(&self, part: usize) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Set the answer to part 1 or part 2
+    pub fn set(&mut self, part: usize, answer: &str) {
+        match part {
+            1 => self.part1 = Some(answer.to_string()),
+            2 => self.part2 = Some(answer.to_string()),
+            _ => {}
+        }
+    }
+}
+
+/// The accepted answers for every day of a year, stored in `answers/<year>.json`
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Ledger {
+    path: PathBuf,
+    days: BTreeMap<usize, Answers>,
+}
+
+impl Ledger {
+    /// Read the ledger for a year. A year without a ledger file has no recorded answers.
+    pub fn load(year: usize) -> Result<Ledger> {
+        Ledger::load_from(ledger_file(year))
+    }
+
+    fn load_from(path: impl AsRef<Path>) -> Result<Ledger> {
+        let path = path.as_ref().to_path_buf();
+        if !path.exists() {
+            return Ok(Ledger {
+                path,
+                days: BTreeMap::new(),
+            });
+        }
+        let contents = read_to_string(&path).map_err(|e| io_error(&path, e))?;
+        let days = serde_json::from_str(&contents).map_err(|e| io_error(&path, e))?;
+        Ok(Ledger { path, days })
+    }
+
+    /// Write the ledger back to the file it was loaded from
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            create_dir_all(dir).map_err(|e| io_error(dir, e))?;
+        }
+        let mut contents =
+            serde_json::to_string_pretty(&self.days).map_err(|e| io_error(&self.path, e))?;
+        contents.push('\n');
+        write(&self.path, contents).map_err(|e| io_error(&self.path, e))
+    }
+
+    /// The recorded answer to a part, if there is one
+    pub fn get(&self, day: usize, part: usize) -> Option<&str> {
+        self.days.get(&day)?.get(part)
+    }
+
+    /// Record the answer to a part, replacing whatever was there before
+    pub fn record(&mut self, day: usize, part: usize, answer: &str) {
+        self.days.entry(day).or_default().set(part, answer);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let path = std::env::temp_dir().join(format!("aoc-ledger-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut ledger = Ledger::load_from(&path).unwrap();
+        assert_eq!(ledger.get(1, 1), None);
+        ledger.record(10, 2, "4");
+        ledger.record(2, 1, "8");
+        ledger.record(2, 1, "2286");
+        ledger.save().unwrap();
+
+        let contents = read_to_string(&path).unwrap();
+        assert!(contents.find("\"2\"").unwrap() < contents.find("\"10\"").unwrap());
+        let loaded = Ledger::load_from(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, ledger);
+        assert_eq!(loaded.get(2, 1), Some("2286"));
+        assert_eq!(loaded.get(2, 2), None);
+        assert_eq!(loaded.get(10, 2), Some("4"));
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt::Write;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use super::pool::Solution;
+use super::AocError;
+
+/// How the CLI prints its results
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum Format {
+    /// Human readable lines
+    #[default]
+    Text,
+    /// A JSON array of records
+    Json,
+    /// One record per line, with a header
+    Csv,
+    /// A table that can be pasted into the README
+    Markdown,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Error,
+    Unimplemented,
+}
+
+/// The outcome of solving one part of one day
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub year: usize,
+    pub day: usize,
+    pub part: usize,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub time_ms: f64,
+}
+
+impl Record {
+    pub fn new(year: usize, day: usize, part: usize, solution: &Solution) -> Record {
+        let (status, answer, error) = match &solution.result {
+            Ok(answer) => (Status::Ok, Some(answer.clone()), None),
+            Err(AocError::Unsolved) => (Status::Unimplemented, None, None),
+            Err(e) => (Status::Error, None, Some(e.to_string())),
+        };
+        Record {
+            year,
+            day,
+            part,
+            status,
+            answer,
+            error,
+            time_ms: solution.elapsed.as_secs_f64() * 1000.0,
+        }
+    }
+
+    fn status(&self) -> &'static str {
+        match self.status {
+            Status::Ok => "ok",
+            Status::Error => "error",
+            Status::Unimplemented => "unimplemented",
+        }
+    }
+}
+
+/// Quote a CSV field if it needs it
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Render the records in a structured format. Text output is printed as the days are solved, so
+/// it is not rendered here.
+pub fn render(format: Format, records: &[Record]) -> String {
+    let mut out = String::new();
+    match format {
+        Format::Text => {}
+        Format::Json => {
+            out = serde_json::to_string_pretty(records).expect("records are serializable");
+            out.push('\n');
+        }
+        Format::Csv => {
+            out.push_str("year,day,part,status,answer,time_ms,error\n");
+            for record in records {
+                let _ = writeln!(
+                    out,
+                    "{},{},{},{},{},{:.3},{}",
+                    record.year,
+                    record.day,
+                    record.part,
+                    record.status(),
+                    csv_field(record.answer.as_deref().unwrap_or("")),
+                    record.time_ms,
+                    csv_field(record.error.as_deref().unwrap_or("")),
+                );
+            }
+        }
+        Format::Markdown => {
+            out.push_str("| Day | Part | Answer | Time | Status |\n");
+            out.push_str("| --: | --: | --: | --: | :-- |\n");
+            for record in records {
+                let answer = match (&record.answer, &record.error) {
+                    (Some(answer), _) => format!("`{answer}`"),
+                    (None, Some(error)) => error.replace('|', "\\|"),
+                    (None, None) => String::new(),
+                };
+                let _ = writeln!(
+                    out,
+                    "| {} | {} | {answer} | {:.3}ms | {} |",
+                    record.day,
+                    record.part,
+                    record.time_ms,
+                    record.status(),
+                );
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    fn records() -> Vec<Record> {
+        let solution = |result| Solution {
+            result,
+            elapsed: Duration::from_micros(1500),
+        };
+        vec![
+            Record::new(2023, 1, 1, &solution(Ok("142".to_string()))),
+            Record::new(
+                2023,
+                1,
+                2,
+                &solution(Err(AocError::solver("no \"digits\", at all"))),
+            ),
+            Record::new(2023, 25, 2, &solution(Err(AocError::Unsolved))),
+        ]
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            render(Format::Csv, &records()),
+            "year,day,part,status,answer,time_ms,error\n\
+             2023,1,1,ok,142,1.500,\n\
+             2023,1,2,error,,1.500,\"Solver failed: no \"\"digits\"\", at all\"\n\
+             2023,25,2,unimplemented,,1.500,\n"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let json: serde_json::Value =
+            serde_json::from_str(&render(Format::Json, &records())).unwrap();
+        assert_eq!(json[0]["answer"], "142");
+        assert_eq!(json[0]["status"], "ok");
+        assert_eq!(json[2]["status"], "unimplemented");
+        assert_eq!(json[2]["answer"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = render(Format::Markdown, &records());
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[2], "| 1 | 1 | `142` | 1.500ms | ok |");
+        assert_eq!(lines[4], "| 25 | 2 |  | 1.500ms | unimplemented |");
+    }
+}
@@ -0,0 +1,123 @@
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{AocError, Result};
+
+/// Turn the payload of a caught panic into an error, keeping the message if there is one
+fn panicked(payload: Box<dyn Any + Send>) -> AocError {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown reason".to_string());
+    AocError::solver(format!("panicked: {message}"))
+}
+
+/// The result of solving one part, and how long it took
+#[derive(Debug)]
+pub struct Solution {
+    pub result: Result<String>,
+    pub elapsed: Duration,
+}
+
+impl Solution {
+    /// Run `solve`, timing only that and not whatever happened before it
+    pub fn timed(solve: impl FnOnce() -> Result<String>) -> Solution {
+        let start = Instant::now();
+        let result = solve();
+        Solution {
+            result,
+            elapsed: start.elapsed(),
+        }
+    }
+    /// A part that failed before it could be solved, like when its input would not parse
+    pub fn failed(error: AocError) -> Solution {
+        Solution {
+            result: Err(error),
+            elapsed: Duration::ZERO,
+        }
+    }
+}
+
+/// Solve both parts of `days` days on a pool of `jobs` worker threads.
+///
+/// `solve(i, part)` solves one part of the `i`th day, timing it with [`Solution::timed`] so that
+/// loading and parsing the input are left out. A panic is caught and becomes an error for that
+/// part only. `report` gets the results of each day in order, as soon as that day and every
+/// day before it are done.
+pub fn solve_all(
+    days: usize,
+    jobs: usize,
+    solve: impl Fn(usize, usize) -> Solution + Sync,
+    mut report: impl FnMut(usize, [Solution; 2]),
+) {
+    let next_task = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, 2 * days.max(1)) {
+            let sender = sender.clone();
+            let (next_task, solve) = (&next_task, &solve);
+            scope.spawn(move || loop {
+                let task = next_task.fetch_add(1, Ordering::Relaxed);
+                if task >= 2 * days {
+                    break;
+                }
+                let (i, part) = (task / 2, task % 2 + 1);
+                let solution = catch_unwind(AssertUnwindSafe(|| solve(i, part)))
+                    .unwrap_or_else(|payload| Solution::failed(panicked(payload)));
+                if sender.send((i, part, solution)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut done: Vec<[Option<Solution>; 2]> = (0..days).map(|_| [None, None]).collect();
+        let mut reported = 0;
+        for (i, part, solution) in receiver {
+            done[i][part - 1] = Some(solution);
+            while reported < days && done[reported].iter().all(Option::is_some) {
+                let [part1, part2] = std::mem::take(&mut done[reported]);
+                report(reported, [part1.unwrap(), part2.unwrap()]);
+                reported += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_results_are_in_order() {
+        let mut reported = vec![];
+        solve_all(
+            6,
+            4,
+            |i, part| {
+                // Make the early days the slowest
+                thread::sleep(Duration::from_millis(10 * (6 - i as u64)));
+                if i == 2 && part == 2 {
+                    panic!("day {i} exploded");
+                }
+                Solution::timed(|| Ok(format!("{i}.{part}")))
+            },
+            |i, solutions| reported.push((i, solutions.map(|solution| solution.result))),
+        );
+        let days: Vec<usize> = reported.iter().map(|(i, _)| *i).collect();
+        assert_eq!(days, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(reported[1].1[0].as_ref().unwrap(), "1.1");
+        assert_eq!(reported[5].1[1].as_ref().unwrap(), "5.2");
+        assert_eq!(reported[2].1[0].as_ref().unwrap(), "2.1");
+        match &reported[2].1[1] {
+            Err(AocError::Solver(message)) => assert_eq!(message, "panicked: day 2 exploded"),
+            other => panic!("expected a caught panic, got {other:?}"),
+        }
+    }
+}
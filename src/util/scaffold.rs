@@ -0,0 +1,186 @@
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+use super::{download_input, examples, io_error, AocError, Result};
+
+/// The solution file that new days start from
+const TEMPLATE: &str = include_str!("../../template.rs");
+const LIB_FILE: &str = "src/lib.rs";
+
+/// Fill in the year and day of the template
+pub fn render(template: &str, year: usize, day: usize) -> String {
+    template
+        .replace("${YEAR}", &year.to_string())
+        .replace("${DAY}", &day.to_string())
+}
+
+/// Add a day to the `days!` list in the source of `lib.rs`.
+/// Returns the new source, and the directory the year keeps its days in, if it has one.
+pub fn register(
+    lib: &str,
+    year: usize,
+    day: usize,
+    title: &str,
+) -> Result<(String, Option<String>)> {
+    let header = Regex::new(&format!(r"^\s*{year}(?:\s+in\s+(\w+))?\s*\{{\s*$")).unwrap();
+    let entry = Regex::new(r"^\s*(\d+)\s*=>").unwrap();
+    let title = title.replace('\\', "\\\\").replace('"', "\\\"");
+    let line = format!("        {day} => day{day}::Day{day}, \"{title}\";");
+    let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+
+    let Some((start, captures)) = lines
+        .iter()
+        .enumerate()
+        .find_map(|(i, line)| header.captures(line).map(|captures| (i, captures)))
+    else {
+        // The first day of a new year, which gets its own directory
+        let dir = format!("year{year}");
+        let days = lines
+            .iter()
+            .position(|line| line.starts_with("days!"))
+            .ok_or_else(|| AocError::Io(format!("{LIB_FILE} has no days! list")))?;
+        let end = days
+            + lines[days..]
+                .iter()
+                .position(|line| line == "}")
+                .ok_or_else(|| AocError::Io(format!("{LIB_FILE} has an unclosed days! list")))?;
+        let block = [format!("    {year} in {dir} {{"), line, "    }".to_string()];
+        lines.splice(end..end, block);
+        return Ok((lines.join("\n") + "\n", Some(dir)));
+    };
+    let dir = captures.get(1).map(|dir| dir.as_str().to_string());
+
+    let mut insert_at = None;
+    for (i, existing) in lines.iter().enumerate().skip(start + 1) {
+        if existing.trim() == "}" {
+            insert_at.get_or_insert(i);
+            break;
+        }
+        if let Some(existing_day) = entry.captures(existing) {
+            let existing_day: usize = existing_day[1].parse().unwrap_or(0);
+            if existing_day == day {
+                return Err(AocError::Usage(format!(
+                    "{year} day {day} is already registered in {LIB_FILE}"
+                )));
+            }
+            if existing_day > day && insert_at.is_none() {
+                insert_at = Some(i);
+            }
+        }
+    }
+    let insert_at =
+        insert_at.ok_or_else(|| AocError::Io(format!("{LIB_FILE} has an unclosed {year} list")))?;
+    lines.insert(insert_at, line);
+    Ok((lines.join("\n") + "\n", dir))
+}
+
+/// Where the solution file of a day lives
+fn day_file(dir: Option<&str>, day: usize) -> PathBuf {
+    let src = Path::new("src");
+    match dir {
+        Some(dir) => src.join(dir).join(format!("day{day}.rs")),
+        None => src.join(format!("day{day}.rs")),
+    }
+}
+
+/// Create the solution file of a day from the template and register it in `lib.rs`.
+/// Existing files are never overwritten. With `pull`, the title comes from the puzzle page,
+/// and the examples and the input are downloaded too.
+pub fn new_day(year: usize, day: usize, title: Option<&str>, pull: bool) -> Result<PathBuf> {
+    if !(1..=25).contains(&day) {
+        return Err(AocError::Usage(format!(
+            "there is no day {day} in Advent of Code"
+        )));
+    }
+    let lib_path = Path::new(LIB_FILE);
+    let lib = read_to_string(lib_path).map_err(|e| io_error(lib_path, e))?;
+    // Check that the day is new before downloading anything
+    let (_, dir) = register(&lib, year, day, "")?;
+    let path = day_file(dir.as_deref(), day);
+    if path.exists() {
+        return Err(AocError::Usage(format!(
+            "{} already exists, refusing to overwrite it",
+            path.display()
+        )));
+    }
+
+    let mut title = title.map(String::from);
+    if pull {
+        let html = examples::download_puzzle_page(year, day)?;
+        title = title.or_else(|| examples::extract_title(&html));
+        examples::save_examples(year, day, &examples::extract_examples(&html))?;
+        download_input(year, day)?;
+    }
+    let title = title.unwrap_or_else(|| format!("Day {day}"));
+    let (lib, _) = register(&lib, year, day, &title)?;
+
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+    }
+    write(&path, render(TEMPLATE, year, day)).map_err(|e| io_error(&path, e))?;
+    write(lib_path, lib).map_err(|e| io_error(lib_path, e))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const LIB: &str = "pub mod util;
+
+days! {
+    2023 {
+        1 => day1::Day1, \"Trebuchet?!\";
+        3 => day3::Day3, \"Gear Ratios\";
+    }
+}
+";
+
+    #[test]
+    fn test_render() {
+        let rendered = render(TEMPLATE, 2023, 7);
+        assert!(rendered.contains("pub struct Day7();"));
+        assert!(rendered.contains("impl DaySolver<Solution> for Day7 {"));
+        assert!(!rendered.contains('$'));
+    }
+
+    #[test]
+    fn test_register_in_order() {
+        let (lib, dir) = register(LIB, 2023, 2, "Cube \"Conundrum\"").unwrap();
+        assert_eq!(dir, None);
+        let lines: Vec<&str> = lib.lines().collect();
+        assert_eq!(lines[4], "        1 => day1::Day1, \"Trebuchet?!\";");
+        assert_eq!(
+            lines[5],
+            "        2 => day2::Day2, \"Cube \\\"Conundrum\\\"\";"
+        );
+        assert_eq!(lines[6], "        3 => day3::Day3, \"Gear Ratios\";");
+
+        let (lib, _) = register(LIB, 2023, 4, "Scratchcards").unwrap();
+        assert_eq!(
+            lib.lines().nth(6),
+            Some("        4 => day4::Day4, \"Scratchcards\";")
+        );
+        assert_eq!(lib.lines().nth(7), Some("    }"));
+    }
+
+    #[test]
+    fn test_register_twice() {
+        assert!(register(LIB, 2023, 3, "Gear Ratios").is_err());
+    }
+
+    #[test]
+    fn test_register_new_year() {
+        let (lib, dir) = register(LIB, 2015, 1, "Not Quite Lisp").unwrap();
+        assert_eq!(dir.as_deref(), Some("year2015"));
+        assert!(lib.ends_with(
+            "    }\n    2015 in year2015 {\n        1 => day1::Day1, \"Not Quite Lisp\";\n    }\n}\n"
+        ));
+        let (lib, dir) = register(&lib, 2015, 2, "I Was Told There Would Be No Math").unwrap();
+        assert_eq!(dir.as_deref(), Some("year2015"));
+        assert!(lib
+            .contains("        2 => day2::Day2, \"I Was Told There Would Be No Math\";\n    }\n}"));
+    }
+}
@@ -0,0 +1,102 @@
+use std::thread::sleep;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::{AocError, Result};
+
+/// Puzzles unlock at midnight US Eastern time (UTC-5, since December is outside daylight saving)
+const UNLOCK_HOUR_UTC: u64 = 5;
+/// Give the website a moment after midnight before asking for the new puzzle
+const GRACE: Duration = Duration::from_secs(2);
+
+/// Days since 1970-01-01 of a date in the Gregorian calendar.
+/// This is Howard Hinnant's `days_from_civil`.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// When the puzzle for a day is released
+pub fn unlock_time(year: usize, day: usize) -> SystemTime {
+    let days = days_from_civil(year as i64, 12, day as i64) as u64;
+    UNIX_EPOCH + Duration::from_secs(days * 86400 + UNLOCK_HOUR_UTC * 3600)
+}
+
+/// How long until the puzzle unlocks at time `now`, or `None` if it already has
+pub fn until_unlock(year: usize, day: usize, now: SystemTime) -> Option<Duration> {
+    unlock_time(year, day).duration_since(now).ok()
+}
+
+/// Format a duration like "3h 2m 1s"
+pub fn countdown(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    match (hours, minutes) {
+        (0, 0) => format!("{seconds}s"),
+        (0, _) => format!("{minutes}m {seconds}s"),
+        _ => format!("{hours}h {minutes}m {seconds}s"),
+    }
+}
+
+/// Refuse to go any further if the puzzle has not unlocked yet
+pub fn ensure_unlocked(year: usize, day: usize) -> Result<()> {
+    match until_unlock(year, day, SystemTime::now()) {
+        Some(wait) => Err(AocError::Locked { year, day, wait }),
+        None => Ok(()),
+    }
+}
+
+/// Sleep until the puzzle unlocks, counting down every so often
+pub fn wait_for_unlock(year: usize, day: usize) {
+    while let Some(wait) = until_unlock(year, day, SystemTime::now()) {
+        eprintln!("{year} day {day} unlocks in {}", countdown(wait));
+        // Count down more often as the unlock gets closer
+        let step = match wait.as_secs() {
+            0..=60 => wait,
+            61..=3600 => Duration::from_secs(60),
+            _ => Duration::from_secs(3600),
+        };
+        sleep(step.min(wait));
+    }
+    sleep(GRACE);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_unlock_time() {
+        // 2023-12-01T05:00:00Z
+        assert_eq!(
+            unlock_time(2023, 1),
+            UNIX_EPOCH + Duration::from_secs(1701406800)
+        );
+        // 2015-12-25T05:00:00Z
+        assert_eq!(
+            unlock_time(2015, 25),
+            UNIX_EPOCH + Duration::from_secs(1451019600)
+        );
+    }
+
+    #[test]
+    fn test_until_unlock() {
+        let unlock = unlock_time(2023, 5);
+        assert_eq!(
+            until_unlock(2023, 5, unlock - Duration::from_secs(90)),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(until_unlock(2023, 5, unlock + Duration::from_secs(1)), None);
+        assert_eq!(until_unlock(2023, 4, unlock), None);
+    }
+
+    #[test]
+    fn test_countdown() {
+        assert_eq!(countdown(Duration::from_secs(5)), "5s");
+        assert_eq!(countdown(Duration::from_secs(98)), "1m 38s");
+        assert_eq!(countdown(Duration::from_secs(3 * 3600 + 121)), "3h 2m 1s");
+    }
+}
@@ -0,0 +1,165 @@
+use std::env;
+use std::fs::{create_dir_all, read_to_string, remove_file, OpenOptions};
+use std::io::{stdin, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use super::http::Http;
+use super::{io_error, AocError, Result};
+
+/// Takes priority over any saved cookie
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const DEFAULT_PROFILE: &str = "default";
+/// Where the cookie used to be saved, in the working directory
+const LEGACY_COOKIE_FILE: &str = ".cookie";
+
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Use the cookie of a named profile for the rest of the run, so that people sharing a machine
+/// can each use their own account. Can only be called once, before any cookie is needed.
+pub fn select_profile(name: &str) -> Result<()> {
+    let valid = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    if name.is_empty() || !name.chars().all(valid) {
+        return Err(AocError::Usage(format!(
+            "profile names can only use letters, digits, - and _, not {name:?}"
+        )));
+    }
+    PROFILE
+        .set(name.to_string())
+        .map_err(|_| AocError::Usage("the profile was already selected".to_string()))
+}
+
+fn profile() -> &'static str {
+    PROFILE.get().map_or(DEFAULT_PROFILE, String::as_str)
+}
+
+/// `$XDG_CONFIG_HOME/advent-of-code`, falling back to `~/.config/advent-of-code`
+fn config_dir_from(xdg_config_home: Option<PathBuf>, home: Option<PathBuf>) -> Option<PathBuf> {
+    let base = xdg_config_home
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| home.map(|home| home.join(".config")))?;
+    Some(base.join("advent-of-code"))
+}
+
+fn cookie_file(profile: &str) -> Result<PathBuf> {
+    let dir = config_dir_from(
+        env::var_os("XDG_CONFIG_HOME").map(PathBuf::from),
+        env::var_os("HOME").map(PathBuf::from),
+    )
+    .ok_or_else(|| AocError::Usage("cannot find a config directory".to_string()))?;
+    Ok(dir.join("sessions").join(profile))
+}
+
+/// Write the cookie so that only the current user can read it
+fn save_cookie(path: &Path, token: &str) -> Result<()> {
+    let dir = path.parent().expect("cookie files are in a directory");
+    create_dir_all(dir).map_err(|e| io_error(dir, e))?;
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))
+            .map_err(|e| io_error(dir, e))?;
+    }
+    let mut file = options.open(path).map_err(|e| io_error(path, e))?;
+    // The mode only applies to new files, so tighten up any file that was already there
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))
+            .map_err(|e| io_error(path, e))?;
+    }
+    file.write_all(token.as_bytes())
+        .map_err(|e| io_error(path, e))
+}
+
+/// Get the session cookie from `AOC_SESSION` or the selected profile, or query the user for it
+pub fn cookie() -> Result<String> {
+    if let Some(token) = env::var(SESSION_VAR)
+        .ok()
+        .filter(|token| !token.trim().is_empty())
+    {
+        return Ok(token.trim().to_string());
+    }
+    let path = cookie_file(profile())?;
+    let legacy_path = PathBuf::from(LEGACY_COOKIE_FILE);
+    if !path.exists() && profile() == DEFAULT_PROFILE && legacy_path.exists() {
+        let token = read_to_string(&legacy_path).map_err(|e| io_error(&legacy_path, e))?;
+        save_cookie(&path, token.trim())?;
+        remove_file(&legacy_path).map_err(|e| io_error(&legacy_path, e))?;
+        eprintln!("Moved the session cookie to {}", path.display());
+    }
+    if path.exists() {
+        let token = read_to_string(&path).map_err(|e| io_error(&path, e))?;
+        return Ok(token.trim().to_string());
+    }
+    eprintln!(
+        "Please paste the contents of the session cookie for the {} profile: ",
+        profile()
+    );
+    let mut buffer = String::new();
+    stdin()
+        .read_line(&mut buffer)
+        .map_err(|e| AocError::MissingInput(format!("cookie: {e}")))?;
+    let token = buffer.trim();
+    if token.is_empty() {
+        return Err(AocError::MissingInput(
+            "no session cookie was given".to_string(),
+        ));
+    }
+    save_cookie(&path, token)?;
+    Ok(token.to_string())
+}
+
+/// Delete the saved cookie of the selected profile
+pub fn clear_cookie() {
+    eprintln!("Clearing the session cookie for the {} profile", profile());
+    // Failing is fine, maybe the file doesn't exist
+    if let Ok(path) = cookie_file(profile()) {
+        let _ = remove_file(path);
+    }
+    let _ = remove_file(LEGACY_COOKIE_FILE);
+    if env::var_os(SESSION_VAR).is_some() {
+        eprintln!("{SESSION_VAR} is set, and will still be used");
+    }
+}
+
+/// Make sure the website accepts the session cookie, by loading a small page that needs it
+pub fn check_cookie() -> Result<()> {
+    Http::shared().get("/settings", &cookie()?).map(|_| ())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_config_dir() {
+        assert_eq!(
+            config_dir_from(Some("/xdg".into()), Some("/home/me".into())),
+            Some(PathBuf::from("/xdg/advent-of-code"))
+        );
+        // Relative XDG paths are invalid and should be ignored
+        assert_eq!(
+            config_dir_from(Some("xdg".into()), Some("/home/me".into())),
+            Some(PathBuf::from("/home/me/.config/advent-of-code"))
+        );
+        assert_eq!(config_dir_from(None, None), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_save_cookie_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = env::temp_dir().join(format!("aoc-session-{}", std::process::id()));
+        let path = dir.join("sessions").join("alice");
+        save_cookie(&path, "abc123").unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        let contents = read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(contents, "abc123");
+    }
+}
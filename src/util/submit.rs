@@ -0,0 +1,181 @@
+use std::fmt::Display;
+use std::time::Duration;
+
+use super::http::Http;
+use super::session::cookie;
+use super::{html_text, AocError, Result};
+
+/// What the website thought of a submitted answer
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about which direction
+    Wrong,
+    /// An answer was submitted too recently, try again after waiting this long
+    RateLimited(Duration),
+    /// The part was already solved, or part 1 has not been solved yet
+    WrongLevel,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "That's the right answer!"),
+            Outcome::TooHigh => write!(f, "That's not the right answer, it's too high"),
+            Outcome::TooLow => write!(f, "That's not the right answer, it's too low"),
+            Outcome::Wrong => write!(f, "That's not the right answer"),
+            Outcome::RateLimited(wait) => {
+                write!(f, "Answered too recently, wait {}s", wait.as_secs())
+            }
+            Outcome::WrongLevel => write!(f, "Not at this level, was it already solved?"),
+        }
+    }
+}
+
+impl Outcome {
+    /// Read the outcome out of the HTML page that the website replies with
+    pub fn from_response(html: &str) -> Option<Outcome> {
+        let text = article_text(html)?;
+        if text.contains("That's the right answer") {
+            Some(Outcome::Correct)
+        } else if text.contains("That's not the right answer") {
+            if text.contains("too high") {
+                Some(Outcome::TooHigh)
+            } else if text.contains("too low") {
+                Some(Outcome::TooLow)
+            } else {
+                Some(Outcome::Wrong)
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Some(Outcome::RateLimited(wait_time(&text).unwrap_or_default()))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Some(Outcome::WrongLevel)
+        } else {
+            None
+        }
+    }
+}
+
+/// The text content of the first `<article>`, which is where the website explains itself
+fn article_text(html: &str) -> Option<String> {
+    let start = html.find("<article")?;
+    let end = html[start..].find("</article>")? + start;
+    Some(html_text(&html[start..end]))
+}
+
+/// Parse a wait time like "You have 1m 38s left to wait"
+fn wait_time(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = text[start..].find(" left to wait")? + start;
+    let mut seconds = 0;
+    for part in text[start..end].split_whitespace() {
+        let unit = match part.chars().last()? {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        seconds += part[..part.len() - 1].parse::<u64>().ok()? * unit;
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+fn submit_path(year: usize, day: usize) -> String {
+    format!("/{year}/day/{day}/answer")
+}
+
+/// Post an answer to a website that behaves like Advent of Code
+pub fn submit_to(
+    http: &Http,
+    session: &str,
+    year: usize,
+    day: usize,
+    part: usize,
+    answer: &str,
+) -> Result<Outcome> {
+    let level = part.to_string();
+    let form = [("level", level.as_str()), ("answer", answer)];
+    let html = http.post_form(&submit_path(year, day), session, &form)?;
+    Outcome::from_response(&html)
+        .ok_or_else(|| AocError::Http("the response did not say whether it was right".into()))
+}
+
+/// Post an answer to Advent of Code with the cached session cookie
+pub fn submit(year: usize, day: usize, part: usize, answer: &str) -> Result<Outcome> {
+    eprintln!("Submitting {answer} for {year} day {day} part {part}");
+    submit_to(Http::shared(), &cookie()?, year, day, part, answer)
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::http::stand_in::serve;
+    use super::*;
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main>\n<article><p>{message}</p></article>\n</main></body></html>")
+    }
+
+    #[test]
+    fn test_submit_correct() {
+        let (http, server) = serve(vec![(
+            200,
+            page("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to restoring snow operations."),
+        )]);
+        let outcome = submit_to(&http, "abc123", 2023, 7, 2, "6440").unwrap();
+        assert_eq!(outcome, Outcome::Correct);
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2023/day/7/answer");
+        assert_eq!(requests[0].cookie, "session=abc123");
+        assert_eq!(requests[0].body, "level=2&answer=6440");
+    }
+
+    #[test]
+    fn test_submit_too_high() {
+        let (http, server) = serve(vec![(
+            200,
+            page("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again. <a href=\"/2023/day/1\">[Return to Day 1]</a>"),
+        )]);
+        let outcome = submit_to(&http, "abc123", 2023, 1, 1, "999999").unwrap();
+        assert_eq!(outcome, Outcome::TooHigh);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_submit_unrecognized() {
+        let (http, server) = serve(vec![(200, "<html>Nothing to see here.</html>".to_string())]);
+        assert!(matches!(
+            submit_to(&http, "abc123", 2023, 1, 1, "1"),
+            Err(AocError::Http(_))
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_outcomes() {
+        assert_eq!(
+            Outcome::from_response(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Some(Outcome::TooLow)
+        );
+        assert_eq!(
+            Outcome::from_response(&page("That's not the right answer.  If you're stuck...")),
+            Some(Outcome::Wrong)
+        );
+        assert_eq!(
+            Outcome::from_response(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 38s left to wait. <a href=\"/2023/day/1\">[Return to Day 1]</a>"
+            )),
+            Some(Outcome::RateLimited(Duration::from_secs(98)))
+        );
+        assert_eq!(
+            Outcome::from_response(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Some(Outcome::WrongLevel)
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::{io_error, AocError, InputSource, Result, Solver};
+
+/// How long each stage of a day took, in milliseconds
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DayTiming {
+    pub day: usize,
+    pub load_ms: f64,
+    pub parse_ms: f64,
+    pub part1_ms: Option<f64>,
+    pub part2_ms: Option<f64>,
+}
+
+impl DayTiming {
+    pub fn total_ms(&self) -> f64 {
+        self.load_ms + self.parse_ms + self.part1_ms.unwrap_or(0.0) + self.part2_ms.unwrap_or(0.0)
+    }
+}
+
+/// The timings of a run over several days, averaged over `repeat` runs of each stage
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimingReport {
+    pub year: usize,
+    pub repeat: usize,
+    pub days: Vec<DayTiming>,
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Run `stage` `repeat` times, returning the last result and the mean time it took
+fn time_stage<T>(repeat: usize, mut stage: impl FnMut() -> Result<T>) -> Result<(T, f64)> {
+    let mut total = Duration::ZERO;
+    let mut result = None;
+    for _ in 0..repeat.max(1) {
+        // Dropping the last result is not part of the stage
+        drop(result.take());
+        let start = Instant::now();
+        let stage_result = stage();
+        total += start.elapsed();
+        result = Some(stage_result?);
+    }
+    let result = result.expect("stages run at least once");
+    Ok((result, millis(total) / repeat.max(1) as f64))
+}
+
+/// Time loading the input, parsing it, and solving each implemented part of a day
+pub fn time_day(solver: &Solver, source: &InputSource, repeat: usize) -> Result<DayTiming> {
+    if solver.implemented == [false, false] {
+        return Err(AocError::Unsolved);
+    }
+    // Make sure the input is cached, so that downloading it is not timed
+    source.read(solver.year, solver.day)?;
+    let (input, load_ms) = time_stage(repeat, || source.read(solver.year, solver.day))?;
+    let (parsed, parse_ms) = time_stage(repeat, || (solver.parse)(&input))?;
+    let mut parts = [None, None];
+    for (i, time) in parts.iter_mut().enumerate() {
+        if let Some(run) = solver.part(i + 1) {
+            let (_, ms) = time_stage(repeat, || run(&parsed))?;
+            *time = Some(ms);
+        }
+    }
+    Ok(DayTiming {
+        day: solver.day,
+        load_ms,
+        parse_ms,
+        part1_ms: parts[0],
+        part2_ms: parts[1],
+    })
+}
+
+impl TimingReport {
+    /// A table of the days, slowest first, followed by the total of each column
+    pub fn table(&self) -> String {
+        let mut days: Vec<&DayTiming> = self.days.iter().collect();
+        days.sort_by(|a, b| b.total_ms().total_cmp(&a.total_ms()));
+        let cell = |ms: Option<f64>| ms.map_or("-".to_string(), |ms| format!("{ms:.3}ms"));
+
+        let mut table = String::new();
+        let _ = writeln!(
+            table,
+            "{:>5} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            "Day", "Load", "Parse", "Part 1", "Part 2", "Total"
+        );
+        for timing in &days {
+            let _ = writeln!(
+                table,
+                "{:>5} {:>12} {:>12} {:>12} {:>12} {:>12}",
+                timing.day,
+                cell(Some(timing.load_ms)),
+                cell(Some(timing.parse_ms)),
+                cell(timing.part1_ms),
+                cell(timing.part2_ms),
+                cell(Some(timing.total_ms())),
+            );
+        }
+        let sum = |stage: fn(&DayTiming) -> Option<f64>| days.iter().filter_map(|t| stage(t)).sum();
+        let _ = writeln!(
+            table,
+            "{:>5} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            "Total",
+            cell(Some(sum(|t| Some(t.load_ms)))),
+            cell(Some(sum(|t| Some(t.parse_ms)))),
+            cell(Some(sum(|t| t.part1_ms))),
+            cell(Some(sum(|t| t.part2_ms))),
+            cell(Some(sum(|t| Some(t.total_ms())))),
+        );
+        if self.repeat > 1 {
+            let _ = writeln!(table, "Mean of {} runs of each stage", self.repeat);
+        }
+        table
+    }
+
+    /// Save the report as JSON, so that it can be compared with later runs
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| io_error(path, e))?;
+        std::fs::write(path, json + "\n").map_err(|e| io_error(path, e))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_table() {
+        let report = TimingReport {
+            year: 2023,
+            repeat: 1,
+            days: vec![
+                DayTiming {
+                    day: 1,
+                    load_ms: 0.5,
+                    parse_ms: 0.5,
+                    part1_ms: Some(1.0),
+                    part2_ms: Some(2.0),
+                },
+                DayTiming {
+                    day: 25,
+                    load_ms: 0.5,
+                    parse_ms: 1.0,
+                    part1_ms: Some(100.0),
+                    part2_ms: None,
+                },
+            ],
+        };
+        let table = report.table();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].trim_start().starts_with("25 "));
+        assert!(lines[1].contains(" - "));
+        assert!(lines[2].trim_start().starts_with("1 "));
+        assert!(lines[3].starts_with("Total"));
+        assert!(lines[3].ends_with("105.500ms"));
+    }
+}
@@ -0,0 +1,174 @@
+use std::fs::{metadata, read_dir};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread::sleep;
+use std::time::{Duration, SystemTime};
+
+use super::examples::examples_dir;
+use super::output::{Record, Status};
+use super::{input_file, AocError, InputSource, Result};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The files that a day reads its input from: the cached input, or the file it was given, and
+/// every example of the day
+pub fn watched_files(year: usize, day: usize, source: &InputSource) -> Vec<PathBuf> {
+    let mut files = vec![match source {
+        InputSource::File(path) => path.clone(),
+        _ => input_file(year, day),
+    }];
+    let prefix = format!("day{day}-");
+    if let Ok(entries) = read_dir(examples_dir(year)) {
+        let mut examples: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                name.starts_with(&prefix) && name.ends_with(".txt")
+            })
+            .collect();
+        examples.sort();
+        files.extend(examples);
+    }
+    files
+}
+
+/// Polls files for changes to their modification time
+pub struct Watcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl Watcher {
+    pub fn new(files: Vec<PathBuf>) -> Watcher {
+        let files = files
+            .into_iter()
+            .map(|path| {
+                let time = modified(&path);
+                (path, time)
+            })
+            .collect();
+        Watcher { files }
+    }
+
+    /// The files that changed since the last call
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = vec![];
+        for (path, last) in self.files.iter_mut() {
+            let time = modified(path);
+            if time != *last {
+                *last = time;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+
+    /// Block until at least one file changes
+    pub fn wait(&mut self) -> Vec<PathBuf> {
+        loop {
+            let changed = self.changed();
+            if !changed.is_empty() {
+                return changed;
+            }
+            sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/// Run this program again in a child process with `args` and `--format json` added, so that
+/// even a crash cannot end the watch session, and read the records it prints
+pub fn run_child(args: &[String]) -> Result<Vec<Record>> {
+    let exe = std::env::current_exe().map_err(|e| AocError::Io(format!("current exe: {e}")))?;
+    let output = Command::new(exe)
+        .args(args)
+        .args(["--format", "json"])
+        .output()
+        .map_err(|e| AocError::Io(format!("running the solver: {e}")))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    match serde_json::from_str(&stdout) {
+        Ok(records) => Ok(records),
+        Err(_) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason = stderr.lines().last().unwrap_or("no output").to_string();
+            Err(AocError::solver(format!("{}: {reason}", output.status)))
+        }
+    }
+}
+
+fn describe(record: &Record) -> String {
+    match record.status {
+        Status::Ok => record.answer.clone().unwrap_or_default(),
+        Status::Error => format!("failed ({})", record.error.as_deref().unwrap_or("?")),
+        Status::Unimplemented => "unimplemented".to_string(),
+    }
+}
+
+/// A line comparing the newest result of a part with the previous one
+pub fn compare(record: &Record, previous: Option<&Record>) -> String {
+    let mut line = format!("Part {}: {}", record.part, describe(record));
+    if let Some(previous) = previous {
+        if previous.answer == record.answer && previous.status == record.status {
+            line.push_str(" (unchanged)");
+        } else {
+            line.push_str(&format!(" (was {})", describe(previous)));
+        }
+    }
+    line.push_str(&format!("  {:.3}ms", record.time_ms));
+    if let Some(previous) = previous {
+        line.push_str(&format!(" (was {:.3}ms)", previous.time_ms));
+    }
+    line
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn record(answer: Option<&str>, time_ms: f64) -> Record {
+        Record {
+            year: 2023,
+            day: 17,
+            part: 1,
+            status: if answer.is_some() {
+                Status::Ok
+            } else {
+                Status::Error
+            },
+            answer: answer.map(String::from),
+            error: answer
+                .is_none()
+                .then(|| "Solver failed: panicked: oops".to_string()),
+            time_ms,
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let first = record(Some("102"), 12.0);
+        assert_eq!(compare(&first, None), "Part 1: 102  12.000ms");
+        let same = record(Some("102"), 10.5);
+        assert_eq!(
+            compare(&same, Some(&first)),
+            "Part 1: 102 (unchanged)  10.500ms (was 12.000ms)"
+        );
+        let broken = record(None, 1.0);
+        assert_eq!(
+            compare(&broken, Some(&first)),
+            "Part 1: failed (Solver failed: panicked: oops) (was 102)  1.000ms (was 12.000ms)"
+        );
+    }
+
+    #[test]
+    fn test_watcher() {
+        let path = std::env::temp_dir().join(format!("aoc-watch-{}.txt", std::process::id()));
+        std::fs::write(&path, "1").unwrap();
+        let mut watcher = Watcher::new(vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(watcher.changed(), vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+    }
+}
@@ -1,21 +1,20 @@
 #![allow(unused)]
-use crate::util::DaySolver;
+use crate::util::{AocError, DaySolver, Result};
 
 pub struct Day${DAY}();
 
-impl Day${DAY} {
-    pub fn parse(input: &[String]) -> usize {
-        0
-    }
-}
-
 type Solution = usize;
 impl DaySolver<Solution> for Day${DAY} {
-    fn part1(input: Vec<String>) -> Option<Solution> {
-        let data = Self::parse(&input);
-        None
+    type Parsed = Vec<String>;
+    const IMPLEMENTED: [bool; 2] = [false, false];
+
+    fn parse(input: &str) -> Result<Vec<String>> {
+        Ok(input.lines().map(String::from).collect())
+    }
+    fn part1(input: &Vec<String>) -> Result<Solution> {
+        Err(AocError::Unsolved)
     }
-    fn part2(input: Vec<String>) -> Option<Solution> {
-        None
+    fn part2(input: &Vec<String>) -> Result<Solution> {
+        Err(AocError::Unsolved)
     }
 }
@@ -0,0 +1,91 @@
+//! Runs every registered day on the examples saved by `--pull-examples`, checking each part
+//! against the answer the puzzle page gives for it.
+//!
+//! This has its own harness, since the cases depend on which examples are on disk. A day without
+//! examples, or an example without an answer for a part, is reported as skipped rather than
+//! passed. Pass a filter like `cargo test --test examples -- "day 3"` to run only some cases.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::process::ExitCode;
+
+use advent_of_code_2023::util::examples::load_examples;
+use advent_of_code_2023::util::{solvers, AocError, Solver};
+
+enum Outcome {
+    Passed,
+    Failed(String),
+    Skipped(String),
+}
+
+/// Run one part of a day on one example
+fn run(solver: &Solver, part: usize, input: &str, expected: Option<&str>) -> Outcome {
+    let Some(solve) = solver.part(part) else {
+        return Outcome::Skipped("not implemented".to_string());
+    };
+    let Some(expected) = expected else {
+        return Outcome::Skipped("no expected answer".to_string());
+    };
+    let result = catch_unwind(AssertUnwindSafe(|| solve(&(solver.parse)(input)?)))
+        .unwrap_or_else(|_| Err(AocError::solver("panicked")));
+    match result {
+        Ok(answer) if answer == expected => Outcome::Passed,
+        Ok(answer) => Outcome::Failed(format!("expected {expected}, got {answer}")),
+        Err(e) => Outcome::Failed(e.to_string()),
+    }
+}
+
+fn main() -> ExitCode {
+    let filter = std::env::args().skip(1).find(|arg| !arg.starts_with('-'));
+    let selected = |name: &str| filter.as_ref().is_none_or(|filter| name.contains(filter));
+    let mut cases = vec![];
+    for solver in solvers() {
+        let name = format!("{} day {}", solver.year, solver.day);
+        match load_examples(solver.year, solver.day) {
+            Ok(examples) if examples.is_empty() => {
+                if selected(&name) {
+                    let reason = "no examples, try --pull-examples".to_string();
+                    cases.push((name, Outcome::Skipped(reason)));
+                }
+            }
+            Ok(examples) => {
+                for (k, example) in examples.iter().enumerate() {
+                    for part in 1..=2 {
+                        let name = format!("{name} example {} part {part}", k + 1);
+                        if !selected(&name) {
+                            continue;
+                        }
+                        let outcome = run(solver, part, &example.input, example.answers.get(part));
+                        cases.push((name, outcome));
+                    }
+                }
+            }
+            Err(e) => cases.push((name, Outcome::Failed(e.to_string()))),
+        }
+    }
+
+    println!("\nrunning {} example tests", cases.len());
+    let (mut passed, mut failed, mut skipped) = (0, 0, 0);
+    for (name, outcome) in &cases {
+        match outcome {
+            Outcome::Passed => {
+                passed += 1;
+                println!("test {name} ... ok");
+            }
+            Outcome::Failed(reason) => {
+                failed += 1;
+                println!("test {name} ... FAILED: {reason}");
+            }
+            Outcome::Skipped(reason) => {
+                skipped += 1;
+                println!("test {name} ... skipped ({reason})");
+            }
+        }
+    }
+    let verdict = if failed == 0 { "ok" } else { "FAILED" };
+    println!("\ntest result: {verdict}. {passed} passed; {failed} failed; {skipped} skipped\n");
+    if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}